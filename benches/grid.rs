@@ -0,0 +1,59 @@
+//! The grid scans days 4 and 10 did before the iterator API, next to the calls they make now.
+//!
+//! Puzzle inputs are not checked in, so each day's example is tiled up to about the size of a real
+//! input. Run with `cargo bench --bench grid`.
+use advent_of_code::grid::{Grid, Point};
+use advent_of_code::template::{read_file, Day};
+use criterion::{criterion_group, criterion_main, Criterion};
+use itertools::Itertools;
+use std::hint::black_box;
+
+fn example_grid<T>(day: u8, parse: impl Fn(char) -> T) -> Grid<T>
+where
+    T: Default + Clone + PartialEq + Copy + std::fmt::Display,
+{
+    let input = read_file("examples", Day::new(day).unwrap());
+    Grid::new(input.lines().map(|line| line.chars().map(&parse).collect()).collect())
+}
+
+fn day_04(c: &mut Criterion) {
+    // The 10x10 example as a 140x140 word search.
+    let grid = example_grid(4, |c| c).tile(14, 14);
+
+    let mut group = c.benchmark_group("day 04 part two: find every A");
+    group.bench_function("all_points + get", |b| {
+        b.iter(|| black_box(&grid).all_points().iter().filter(|p| grid.get(p) == Some('A')).count())
+    });
+    group.bench_function("positions_of", |b| b.iter(|| black_box(&grid).positions_of('A').count()));
+    group.finish();
+
+    let mut group = c.benchmark_group("day 04: neighbours of every cell");
+    group.bench_function("neighbors", |b| {
+        b.iter(|| grid.points().map(|p| black_box(&grid).neighbors(&p).len()).sum::<usize>())
+    });
+    group.bench_function("neighbors_iter", |b| {
+        b.iter(|| grid.points().map(|p| black_box(&grid).neighbors_iter(&p).count()).sum::<usize>())
+    });
+    group.finish();
+}
+
+fn day_10(c: &mut Criterion) {
+    // The 8x8 example as a 56x56 topographic map.
+    let grid = example_grid(10, |c| c.to_digit(10).unwrap() as u8).tile(7, 7);
+
+    let mut group = c.benchmark_group("day 10: trailheads");
+    group.bench_function("all_points + get", |b| {
+        b.iter(|| -> Vec<Point> {
+            black_box(&grid)
+                .all_points()
+                .into_iter()
+                .filter(|p| grid.get(p) == Some(0))
+                .collect_vec()
+        })
+    });
+    group.bench_function("positions_of", |b| b.iter(|| black_box(&grid).positions_of(0).collect_vec()));
+    group.finish();
+}
+
+criterion_group!(benches, day_04, day_10);
+criterion_main!(benches);
@@ -0,0 +1,54 @@
+//! Breadth-first search next to the heap-based searches days 18 and 20 used before `bfs` existed.
+//!
+//! Puzzle inputs are not checked in, so this runs on a 71x71 memory grid like day 18's, with one
+//! cell in ten corrupted in a fixed pseudo-random pattern. Run with `cargo bench --bench search`.
+use advent_of_code::grid::{Grid, Point};
+use advent_of_code::search::{bfs, bfs_distance_map, dijkstra, distance_map, distance_map_with_paths};
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+
+const SIZE: usize = 71;
+
+fn memory_grid() -> Grid<char> {
+    let mut grid = Grid::new(vec![vec!['.'; SIZE]; SIZE]);
+    let mut seed: u64 = 2024;
+    for _ in 0..SIZE * SIZE / 10 {
+        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        let cell = (seed >> 33) as usize % (SIZE * SIZE);
+        grid.set(&Point::new((cell / SIZE) as i32, (cell % SIZE) as i32), '#');
+    }
+    grid.set(&Point::new(0, 0), '.');
+    grid.set(&Point::new(SIZE as i32 - 1, SIZE as i32 - 1), '.');
+    grid
+}
+
+fn open_neighbors<'a>(grid: &'a Grid<char>, point: &Point) -> impl Iterator<Item = Point> + 'a {
+    grid.neighbors_iter(point).filter(|n| grid.get(n) == Some('.'))
+}
+
+fn search(c: &mut Criterion) {
+    let grid = memory_grid();
+    let start = Point::new(0, 0);
+    let end = Point::new(SIZE as i32 - 1, SIZE as i32 - 1);
+    assert!(bfs(&start, |p| open_neighbors(&grid, p), |p| *p == end).is_some());
+
+    let mut group = c.benchmark_group("day 18: shortest path");
+    group.bench_function("dijkstra", |b| {
+        b.iter(|| dijkstra(black_box(&start), |p| open_neighbors(&grid, p).map(|n| (n, 1u64)), |p| *p == end))
+    });
+    group.bench_function("bfs", |b| b.iter(|| bfs(black_box(&start), |p| open_neighbors(&grid, p), |p| *p == end)));
+    group.finish();
+
+    let mut group = c.benchmark_group("day 20: distances from the start");
+    group.bench_function("distance_map", |b| {
+        b.iter(|| distance_map([black_box(start)], |p| open_neighbors(&grid, p).map(|n| (n, 1u64))))
+    });
+    group.bench_function("distance_map_with_paths", |b| {
+        b.iter(|| distance_map_with_paths([black_box(start)], |p| open_neighbors(&grid, p).map(|n| (n, 1u64))))
+    });
+    group.bench_function("bfs_distance_map", |b| b.iter(|| bfs_distance_map([black_box(start)], |p| open_neighbors(&grid, p))));
+    group.finish();
+}
+
+criterion_group!(benches, search);
+criterion_main!(benches);
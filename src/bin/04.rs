@@ -41,8 +41,7 @@ fn strings_from_points(grid: &Grid<char>, points: Vec<Vec<Point>>) -> Vec<String
 
 pub fn part_one(input: &str) -> Option<usize> {
     let grid = parse_input(input).unwrap().1;
-    let xs : Vec<Point> = grid.all_points().into_iter().filter(|p|grid.get(p) == Some('X')).collect();
-    let all_paths : Vec<Vec<Point>> = xs.iter().flat_map(|p|all_points_from(p)).collect();
+    let all_paths : Vec<Vec<Point>> = grid.positions_of('X').flat_map(|p|all_points_from(&p)).collect();
     let strs = strings_from_points(&grid, all_paths);
     let count = strs.iter().filter(|s| *s == "MAS").count();
     Some(count)
@@ -51,9 +50,7 @@ pub fn part_one(input: &str) -> Option<usize> {
 pub fn part_two(input: &str) -> Option<usize> {
     let grid = parse_input(input).unwrap().1;
     let result = grid
-        .all_points()
-        .iter()
-        .filter(|p| grid.get(p) == Some('A'))
+        .positions_of('A')
         .filter(|p| {
             let top_left = grid.get(&p.move_direction(&NW));
             let top_right = grid.get(&p.move_direction(&NE));
@@ -1,3 +1,4 @@
+use advent_of_code::search::PartialOrder;
 use itertools::Itertools;
 use nom::bytes::complete::tag;
 use nom::character::complete;
@@ -5,25 +6,18 @@ use nom::combinator::map;
 use nom::multi::separated_list1;
 use nom::sequence::separated_pair;
 use nom::IResult;
-use std::collections::{HashMap, HashSet};
 
 advent_of_code::solution!(5);
 
 #[derive(Debug, PartialEq, Eq)]
 struct Puzzle {
-    rules: HashMap<i64, HashSet<i64>>,
+    rules: PartialOrder<i64>,
     updates: Vec<Vec<i64>>,
 }
 
 fn parse_input(input: &str) -> IResult<&str, Puzzle> {
     let pair = separated_pair(complete::i64, tag("|"), complete::i64);
-    let pairs = map(separated_list1(tag("\n"), pair), |ps| {
-        let mut rule_map: HashMap<i64, HashSet<i64>> = HashMap::new();
-        for p in ps {
-            rule_map.entry(p.0).or_default().insert(p.1);
-        }
-        rule_map
-    });
+    let pairs = map(separated_list1(tag("\n"), pair), |ps| ps.into_iter().collect());
     let update = separated_list1(tag(","), complete::i64);
     let updates = separated_list1(tag("\n"), update);
 
@@ -35,25 +29,8 @@ fn parse_input(input: &str) -> IResult<&str, Puzzle> {
     })(input)
 }
 
-fn violations(update: &Vec<i64>, rules: &HashMap<i64, HashSet<i64>>) -> Vec<(i64, i64)> {
-    let mut seen_so_far = HashSet::new();
-    let mut violations = Vec::new();
-
-    for u in update.iter() {
-        if let Some(should_appear_after) = rules.get(u) {
-            for after in should_appear_after {
-                if seen_so_far.contains(after) {
-                    violations.push((*u, *after));
-                }
-            }
-        }
-        seen_so_far.insert(u);
-    }
-    violations
-}
-
-fn is_allowed(update: &Vec<i64>, rules: &HashMap<i64, HashSet<i64>>) -> bool {
-    violations(update, rules).is_empty()
+fn is_allowed(update: &[i64], rules: &PartialOrder<i64>) -> bool {
+    rules.is_consistent(update)
 }
 
 fn get_middle(update: &Vec<i64>) -> i64 {
@@ -95,16 +72,8 @@ fn swap(update: &Vec<i64>, a: i64, b: i64) -> Option<Vec<i64>> {
     }
 }
 
-fn fix_update(update: &Vec<i64>, rules: &HashMap<i64, HashSet<i64>>) -> Vec<i64> {
-    let mut result = update.clone();
-    result.sort_by(|a, b| {
-        if rules.get(a).unwrap().contains(b) {
-            std::cmp::Ordering::Less
-        } else {
-            std::cmp::Ordering::Greater
-        }
-    });
-    result
+fn fix_update(update: &[i64], rules: &PartialOrder<i64>) -> Vec<i64> {
+    rules.linearize(update).expect("the rules for an update should not contradict each other")
 }
 
 pub fn part_two(input: &str) -> Option<i64> {
@@ -135,4 +104,10 @@ mod tests {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(143));
     }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, Some(123));
+    }
 }
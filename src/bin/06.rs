@@ -1,3 +1,4 @@
+use advent_of_code::cycle;
 use advent_of_code::grid::{Direction, Grid, Point};
 use itertools::Itertools;
 use nom::branch::alt;
@@ -6,7 +7,6 @@ use nom::combinator::{eof, map};
 use nom::multi::many1;
 use nom::sequence::terminated;
 use nom::IResult;
-use std::collections::HashSet;
 
 advent_of_code::solution!(6);
 
@@ -59,10 +59,7 @@ fn step(point: &Point, dir: &Direction, grid: &Grid<char>) -> Vec<Point> {
 }
 
 fn get_start_point(grid: &Grid<char>) -> Point {
-    grid.all_points()
-        .into_iter()
-        .find(|p| grid.get(p) == Some('^'))
-        .unwrap()
+    grid.find(|c| c == '^').unwrap()
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
@@ -73,26 +70,14 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 fn is_cycle(point: &Point, dir: &Direction, grid: &Grid<char>, added_wall: &Point) -> bool {
-    let mut seen_states: HashSet<(Point, Direction)> = HashSet::new();
-    let mut current_point = *point;
-    let mut current_direction = *dir;
-    seen_states.insert((current_point, current_direction));
-
-    loop {
-        (current_point, current_direction) =
-            get_next_point(&current_point, &current_direction, grid, Some(*added_wall));
-
-        if !grid.in_bounds(&current_point) {
-            // The point went out of bounds, so this isn't a cycle.
-            return false;
-        }
-
-        if seen_states.contains(&(current_point, current_direction)) {
-            return true;
-        } else {
-            seen_states.insert((current_point, current_direction));
-        }
-    }
+    // Leaving the map is modelled as a `None` state that the guard never leaves, so the walk
+    // always ends up in a cycle and only a real loop has a guard in it.
+    let step = |state: &Option<(Point, Direction)>| {
+        let (point, direction) = (*state)?;
+        let next = get_next_point(&point, &direction, grid, Some(*added_wall));
+        grid.in_bounds(&next.0).then_some(next)
+    };
+    cycle::detect(&Some((*point, *dir)), step).state.is_some()
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
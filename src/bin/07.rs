@@ -1,3 +1,4 @@
+use advent_of_code::search::Dfs;
 use itertools::Itertools;
 use nom::bytes::complete::tag;
 use nom::character::complete;
@@ -33,28 +34,23 @@ fn parse_input(s: &str) -> IResult<&str, Vec<Puzzle>> {
     separated_list1(tag("\n"), parse_line)(s)
 }
 
-fn can_be_true(ops: &Vec<fn(u64, u64) -> u64>, numbers: &[u64], target: u64) -> bool {
-    fn helper(
-        ops: &Vec<fn(u64, u64) -> u64>,
-        numbers: &[u64],
-        target: u64,
-        current_value: u64,
-        index: usize,
-    ) -> bool {
+fn can_be_true(ops: &[fn(u64, u64) -> u64], numbers: &[u64], target: u64) -> bool {
+    // States are (index of the next number, value so far).
+    let successors = |&(index, value): &(usize, u64)| -> Vec<(usize, u64)> {
         if index == numbers.len() {
-            return current_value == target;
+            return Vec::new();
         }
-
-        if index == 0 {
-            return helper(ops, numbers, target, numbers[0], index + 1);
-        }
-
-        ops.iter().any(|f| {
-            helper(ops, numbers, target, f(current_value, numbers[index]), index + 1)
-        })
-    }
-
-    helper(ops, numbers, target, 0, 0)
+        ops.iter().map(|f| (index + 1, f(value, numbers[index]))).collect()
+    };
+    let is_goal = |&(index, value): &(usize, u64)| index == numbers.len() && value == target;
+
+    // The value only shrinks when it is multiplied by zero, so a value above the target is a dead
+    // end once there are no zeros left to apply. For positive inputs that is every state.
+    let last_zero = numbers.iter().rposition(|&n| n == 0);
+    Dfs::new(successors, is_goal)
+        .prune(|&(index, value)| value > target && last_zero.is_none_or(|zero| zero < index))
+        .find_any(&(1, numbers[0]))
+        .is_some()
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
@@ -114,4 +110,12 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(11387));
     }
+
+    #[test]
+    fn test_zero_brings_value_back_down() {
+        let ops: Vec<fn(u64, u64) -> u64> = vec![|a, b| a + b, |a, b| a * b];
+        // 10 + 20 overshoots, but multiplying by the zero starts over: (10 + 20) * 0 + 3.
+        assert!(can_be_true(&ops, &[10, 20, 0, 3], 3));
+        assert!(!can_be_true(&ops, &[10, 20, 3], 3));
+    }
 }
@@ -56,9 +56,8 @@ fn spread_signal(grid: &Grid<char>, a: &Point, b: &Point) -> HashSet<Point> {
 
 fn get_letters_map(grid: &Grid<char>) -> HashMap<char, HashSet<Point>> {
     let letters: HashMap<char, HashSet<Point>> = grid
-        .all_points()
         .iter()
-        .filter_map(|p| grid.get(p).map(|c| (c, *p)))
+        .map(|(p, &c)| (c, p))
         .filter(|(c, _)| *c != '.')
         .sorted()
         .chunk_by(|(c, _)| *c)
@@ -1,12 +1,12 @@
 use advent_of_code::grid::Direction::{E, N, S, W};
 use advent_of_code::grid::{Grid, Point};
+use advent_of_code::search::{count_paths_from, reachable};
 use itertools::Itertools;
 use nom::bytes::complete::tag;
 use nom::character::complete::one_of;
 use nom::combinator::{map, map_res};
 use nom::multi::{many1, separated_list1};
 use nom::IResult;
-use std::collections::HashSet;
 
 advent_of_code::solution!(10);
 
@@ -35,37 +35,8 @@ fn neighbors(grid: &Grid<u8>, point: &Point) -> Vec<Point> {
     ns
 }
 
-fn reachable_ends(grid: &Grid<u8>, start: &Point) -> HashSet<Point> {
-    if let Some(9) = grid.get(start) {
-        return HashSet::from_iter(vec![*start]);
-    }
-
-    neighbors(grid, start)
-        .iter()
-        .flat_map(|p| reachable_ends(grid, p))
-        .collect()
-}
-
-fn count_reachable_ends(grid: &Grid<u8>, start: &Point) -> usize {
-    if let Some(9) = grid.get(start) {
-        return 1;
-    }
-
-    neighbors(grid, start)
-        .iter()
-        .map(|p| count_reachable_ends(grid, p))
-        .sum()
-}
-
 fn get_start_points(grid: &Grid<u8>) -> Vec<Point> {
-    grid.all_points().into_iter()
-        .filter(|p| {
-            if let Some(0) = grid.get(p) {
-                return true;
-            }
-            false
-        })
-        .collect_vec()
+    grid.positions_of(0).collect_vec()
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
@@ -73,7 +44,12 @@ pub fn part_one(input: &str) -> Option<usize> {
     let starts = get_start_points(&grid);
     let result = starts
         .iter()
-        .map(|p| reachable_ends(&grid, p).iter().count())
+        .map(|p| {
+            reachable(p, |q: &Point| neighbors(&grid, q))
+                .into_iter()
+                .filter(|q| grid.get(q) == Some(9))
+                .count()
+        })
         .sum();
     Some(result)
 }
@@ -81,10 +57,8 @@ pub fn part_one(input: &str) -> Option<usize> {
 pub fn part_two(input: &str) -> Option<usize> {
     let grid = parse_input(input).unwrap().1;
     let starts = get_start_points(&grid);
-    let result = starts
-        .iter()
-        .map(|p| count_reachable_ends(&grid, p))
-        .sum();
+    let is_end = |p: &Point| grid.get(p) == Some(9);
+    let result = count_paths_from(starts, |p: &Point| neighbors(&grid, p), is_end) as usize;
     Some(result)
 }
 
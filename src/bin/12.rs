@@ -1,5 +1,6 @@
 use advent_of_code::grid::Direction::{E, N, S, W};
 use advent_of_code::grid::{Direction, Grid, Point};
+use advent_of_code::union_find::UnionFind;
 use itertools::Itertools;
 use nom::branch::alt;
 use nom::character::complete::{line_ending, none_of};
@@ -12,59 +13,6 @@ use std::iter::successors;
 
 advent_of_code::solution!(12);
 
-struct UnionFind {
-    parent: HashMap<Point, Point>,
-    rank: HashMap<Point, usize>,
-}
-
-impl UnionFind {
-    fn new(points: &Vec<Point>) -> Self {
-        UnionFind {
-            parent: points.iter().map(|&p| (p, p)).collect(),
-            rank: points.iter().map(|&p| (p, 0)).collect(),
-        }
-    }
-
-    fn find(&self, p: &Point) -> Point {
-        let parent = self.parent.get(p).unwrap();
-        if parent == p {
-            *p
-        } else {
-            self.find(parent)
-        }
-    }
-
-    fn union(&mut self, a: &Point, b: &Point) {
-        let root_a = self.find(a);
-        let root_b = self.find(b);
-
-        if root_a == root_b {
-            return;
-        }
-
-        let rank_a = *self.rank.get(&root_a).unwrap();
-        let rank_b = *self.rank.get(&root_b).unwrap();
-
-        if rank_a < rank_b {
-            self.parent.insert(root_a, root_b);
-            self.rank.entry(root_b).and_modify(|r| *r += rank_a);
-        } else {
-            self.parent.insert(root_b, root_a);
-            self.rank.entry(root_a).and_modify(|r| *r += rank_b);
-        }
-    }
-
-    fn connected_components(&self) -> Vec<HashSet<Point>> {
-        let mut root_map: HashMap<Point, HashSet<Point>> = HashMap::new();
-        self.parent.keys().for_each(|p| {
-            let root = self.find(p);
-            root_map.entry(root).or_insert_with(HashSet::new).insert(*p);
-        });
-
-        root_map.values().cloned().collect()
-    }
-}
-
 fn parse_input(input: &str) -> IResult<&str, Grid<char>> {
     let parse_line = terminated(many1(none_of(" \t\n\r")), alt((line_ending, eof)));
     map(many1(parse_line), |data| Grid::new(data))(input)
@@ -90,16 +38,19 @@ fn get_perimeter(grid: &Grid<char>, points: &HashSet<Point>) -> u64 {
 }
 
 fn get_plot_points(grid: &Grid<char>) -> Vec<HashSet<Point>> {
-    let points = grid.all_points();
-    let mut union_find = UnionFind::new(&points);
+    let mut union_find: UnionFind<Point> = grid.points().collect();
 
-    for p in points.iter() {
-        for n in neighbors(grid, *p) {
-            union_find.union(p, &n);
+    for p in grid.points() {
+        for n in neighbors(grid, p) {
+            union_find.union(&p, &n);
         }
     }
 
-    union_find.connected_components()
+    union_find
+        .components()
+        .into_iter()
+        .map(|component| component.into_iter().collect())
+        .collect()
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
@@ -1,5 +1,6 @@
 use advent_of_code::grid::Direction::{E, N, S, W};
 use advent_of_code::grid::{Direction, Grid, Point};
+use advent_of_code::layered::{EntityId, LayeredGrid};
 use itertools::Itertools;
 use nom::branch::alt;
 use nom::bytes::complete::tag;
@@ -37,33 +38,41 @@ fn parse_input(input: &str) -> IResult<&str, (Grid<char>, Vec<Direction>)> {
     separated_pair(parse_grid, tag("\n\n"), parse_dirs)(input) // Accept both double line endings
 }
 
-// Attempts to perform a move. If the move is possible, it will return a list of one or more swaps
+// Returns the tile that is visible at a point: the entity standing there, or the terrain beneath.
+fn tile_at(map: &LayeredGrid<char, char>, point: &Point) -> Option<char> {
+    match map.entity_at(point) {
+        Some((_, &entity)) => Some(entity),
+        None => map.terrain_at(point),
+    }
+}
+
+// Attempts to perform a move. If the move is possible, it will return a list of one or more moves
 // (in the order that they should be performed). If the move is not possible, returns an empty list.
-fn swaps_to_move(grid: &Grid<char>, point: &Point, direction: &Direction) -> Vec<(Point, Point)> {
+fn moves_to_make(map: &LayeredGrid<char, char>, point: &Point, direction: &Direction) -> Vec<(Point, Point)> {
     let next_point = point.move_direction(direction);
-    let next_tile = grid.get(&next_point);
+    let next_tile = tile_at(map, &next_point);
 
-    if next_tile == Some('#') || next_tile == None {
+    if next_tile == Some('#') || next_tile.is_none() {
         return vec![];
     }
     if next_tile == Some('.') {
         return vec![(*point, next_point)];
     }
     if next_tile == Some('O') {
-        let swaps = swaps_to_move(grid, &next_point, direction);
-        return if swaps.is_empty() {
+        let moves = moves_to_make(map, &next_point, direction);
+        return if moves.is_empty() {
             vec![]
         } else {
-            swaps.into_iter().chain(iter::once((*point, next_point))).collect()
+            moves.into_iter().chain(iter::once((*point, next_point))).collect()
         };
     }
     if next_tile == Some('[') || next_tile == Some(']') {
         if direction == &E || direction == &W {
-            let swaps = swaps_to_move(grid, &next_point, direction);
-            return if swaps.is_empty() {
+            let moves = moves_to_make(map, &next_point, direction);
+            return if moves.is_empty() {
                 vec![]
             } else {
-                swaps.into_iter().chain(iter::once((*point, next_point))).collect()
+                moves.into_iter().chain(iter::once((*point, next_point))).collect()
             };
         }
 
@@ -71,65 +80,61 @@ fn swaps_to_move(grid: &Grid<char>, point: &Point, direction: &Direction) -> Vec
         let right = if next_tile == Some(']') { next_point } else { next_point.move_direction(&E) };
 
 
-        let left_swaps = swaps_to_move(grid, &left, direction);
-        let right_swaps = swaps_to_move(grid, &right, direction);
-        if left_swaps.is_empty() || right_swaps.is_empty() {
+        let left_moves = moves_to_make(map, &left, direction);
+        let right_moves = moves_to_make(map, &right, direction);
+        if left_moves.is_empty() || right_moves.is_empty() {
             return vec![];
         }
 
-        let ret = left_swaps.into_iter()
-            .chain(right_swaps)
-            .chain(iter::once((*point, next_point))) // Robot swap LAST
+        let ret = left_moves.into_iter()
+            .chain(right_moves)
+            .chain(iter::once((*point, next_point))) // Robot move LAST
             .unique()
             .collect();
         return ret;
     }
 
 
-    panic!("Invalid next_tile {:?}", grid.get(&next_point));
+    panic!("Invalid next_tile {:?}", next_tile);
 }
 
-fn perform_swaps(grid: &mut Grid<char>, swaps: &Vec<(Point, Point)>) {
-    for swap in swaps {
-        let (a, b) = swap;
-
-        let new_a = grid.get(b).unwrap();
-        let new_b = grid.get(a).unwrap();
-        grid.set(a, new_a);
-        grid.set(b, new_b);
+fn perform_moves(map: &mut LayeredGrid<char, char>, moves: &[(Point, Point)]) {
+    for (from, to) in moves {
+        let (id, _) = map.entity_at(from).unwrap();
+        assert!(map.move_entity(id, to), "{:?} is blocked", to);
     }
 }
 
-fn step(grid: &mut Grid<char>, point: &Point, direction: &Direction) -> Point {
-    let swaps = swaps_to_move(grid, point, direction);
+fn step(map: &mut LayeredGrid<char, char>, robot: EntityId, direction: &Direction) {
+    let moves = moves_to_make(map, &map.position(robot).unwrap(), direction);
 
     if SHOULD_PRINT { println!("Moving {:?}", direction); }
-    if swaps.is_empty() {
-        if SHOULD_PRINT { println!("{}\n", grid.to_string()); }
-        *point
-    } else {
-        let next_point = point.move_direction(direction);
-        perform_swaps(grid, &swaps);
-        if SHOULD_PRINT { println!("{}\n", grid.to_string()); }
-        next_point
+    if !moves.is_empty() {
+        perform_moves(map, &moves);
     }
+    if SHOULD_PRINT { println!("{}\n", map); }
 }
 
-fn gps_score(grid: &Grid<char>) -> u64 {
-    grid.all_points()
-        .iter()
-        .filter(|p| grid.get(p) == Some('O') || grid.get(p) == Some('['))
-        .map(|p| (p.row * 100 + p.col) as u64)
+fn gps_score(map: &LayeredGrid<char, char>) -> u64 {
+    map.entities()
+        .filter(|(_, _, &c)| c == 'O' || c == '[')
+        .map(|(_, p, _)| (p.row * 100 + p.col) as u64)
         .sum()
 }
 
+fn split_layers(grid: &Grid<char>) -> (LayeredGrid<char, char>, EntityId) {
+    let map = LayeredGrid::from_grid(grid, |c| c != '#' && c != '.', '.');
+    let robot = map.entities().find(|(_, _, &c)| c == '@').map(|(id, _, _)| id).unwrap();
+    (map, robot)
+}
+
 pub fn part_one(input: &str) -> Option<u64> {
-    let (mut grid, directions) = parse_input(input).unwrap().1;
-    let mut start = grid.all_points().iter().find(|p| grid.get(p) == Some('@')).unwrap().clone();
+    let (grid, directions) = parse_input(input).unwrap().1;
+    let (mut map, robot) = split_layers(&grid);
     for dir in directions {
-        start = step(&mut grid, &start, &dir);
+        step(&mut map, robot, &dir);
     }
-    Some(gps_score(&grid))
+    Some(gps_score(&map))
 }
 
 fn expand(grid: &Grid<char>) -> Grid<char> {
@@ -145,16 +150,16 @@ fn expand(grid: &Grid<char>) -> Grid<char> {
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let (mut grid, directions) = parse_input(input).unwrap().1;
-    grid = expand(&grid);
+    let (grid, directions) = parse_input(input).unwrap().1;
+    let grid = expand(&grid);
 
-    if SHOULD_PRINT { println!("\n{}\n", grid.to_string()); }
+    if SHOULD_PRINT { println!("\n{}\n", grid); }
 
-    let mut start = grid.all_points().iter().find(|p| grid.get(p) == Some('@')).unwrap().clone();
+    let (mut map, robot) = split_layers(&grid);
     for dir in directions {
-        start = step(&mut grid, &start, &dir);
+        step(&mut map, robot, &dir);
     }
-    Some(gps_score(&grid))
+    Some(gps_score(&map))
 }
 
 #[cfg(test)]
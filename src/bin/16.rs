@@ -1,6 +1,6 @@
 use advent_of_code::grid::Direction::{E, N, S, W};
 use advent_of_code::grid::{Direction, Grid, Point};
-use advent_of_code::search::{dijkstra, dijkstra_all_shortest_paths};
+use advent_of_code::search::{dijkstra_all_shortest_paths_with_visitor, dijkstra_dial, SearchStats};
 use itertools::Itertools;
 use nom::branch::alt;
 use nom::character::complete::{line_ending, none_of};
@@ -67,7 +67,7 @@ pub fn part_one(input: &str) -> Option<u64> {
     let is_goal = |state: &State| state.point == end;
     let nebs = |state: &State| neighbors(&grid, state);
 
-    let (_, cost) = dijkstra(&start_state, &nebs, &is_goal).unwrap();
+    let (_, cost) = dijkstra_dial(&start_state, nebs, is_goal).unwrap();
 
     Some(cost)
 }
@@ -83,10 +83,14 @@ pub fn part_two(input: &str) -> Option<u32> {
     };
     let is_goal = |state: &State| state.point == end;
     let nebs = |state: &State| neighbors(&grid, state);
-    let (paths, _) = dijkstra_all_shortest_paths(&start_state, &nebs, &is_goal).unwrap();
-    let result = paths
+    let mut stats = SearchStats::requested("all shortest paths");
+    let dag = dijkstra_all_shortest_paths_with_visitor(&start_state, nebs, is_goal, &mut stats).unwrap();
+    if let Some(stats) = stats {
+        stats.record();
+    }
+    let result = dag
+        .nodes()
         .iter()
-        .flatten()
         .map(|state| state.point)
         .unique()
         .count() as u32;
@@ -116,4 +120,25 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(45));
     }
+
+    #[test]
+    fn test_search_stats() {
+        use advent_of_code::search::{dijkstra_all_shortest_paths_with_visitor, dijkstra_dial_with_visitor, SearchStats};
+
+        let grid = parse_input(&advent_of_code::template::read_file("examples", DAY)).unwrap().1;
+        let start = State {
+            point: grid.find(|c| c == 'S').unwrap(),
+            heading: E,
+        };
+        let end = grid.find(|c| c == 'E').unwrap();
+
+        let mut dial = SearchStats::new("dial");
+        let cost = dijkstra_dial_with_visitor(&start, |s| neighbors(&grid, s), |s| s.point == end, &mut dial).unwrap().cost;
+        let mut all = SearchStats::new("all shortest paths");
+        dijkstra_all_shortest_paths_with_visitor(&start, |s| neighbors(&grid, s), |s| s.point == end, &mut all).unwrap();
+
+        assert_eq!(cost, 7036);
+        // Collecting every tied route means settling everything up to the goal's distance too.
+        assert!(dial.expanded > 0 && dial.expanded <= all.expanded, "{dial} / {all}");
+    }
 }
@@ -1,6 +1,6 @@
 use advent_of_code::grid::Direction::{E, N, S, W};
 use advent_of_code::grid::{Grid, Point};
-use advent_of_code::search::dijkstra;
+use advent_of_code::search::{bfs, first_true};
 use nom::bytes::complete::tag;
 use nom::character::complete;
 use nom::combinator::{complete, map};
@@ -27,12 +27,11 @@ fn create_grid(walls: Vec<Point>) -> Grid<char> {
     grid
 }
 
-fn neighbors(grid: &Grid<char>, point: &Point) -> Vec<(Point, u64)> {
+fn neighbors(grid: &Grid<char>, point: &Point) -> Vec<Point> {
     [N, S, E, W]
         .into_iter()
         .map(|d| point.move_direction(&d))
         .filter(|p| grid.get(p) == Some('.'))
-        .map(|p| (p, 1))
         .collect()
 }
 
@@ -51,8 +50,8 @@ pub fn part_one(input: &str) -> Option<u64> {
     let is_goal = |point: &Point| *point == end;
     let nebs = |point: &Point| neighbors(&grid, point);
 
-    let (_, cost) = dijkstra(&start, &nebs, &is_goal).unwrap();
-    Some(cost)
+    let result = bfs(&start, &nebs, &is_goal).unwrap();
+    Some(result.cost)
 }
 
 pub fn part_two(input: &str) -> Option<String> {
@@ -61,24 +60,15 @@ pub fn part_two(input: &str) -> Option<String> {
 
     let is_goal = |point: &Point| *point == end;
     let all_walls = parse_input(input).unwrap().1;
-    for i in 0..=all_walls.len() {
-        let walls = all_walls.iter()
-            .take(i)
-            .cloned()
-            .collect();
-        let grid = create_grid(walls);
-        let nebs = |point: &Point| neighbors(&grid, point);
-        if dijkstra(&start, nebs, &is_goal).is_none() {
-            if i > 0 {
-                let blocking_point = all_walls.get(i - 1).unwrap();
-                return Some(format!("{},{}", blocking_point.row, blocking_point.col));
-            } else {
-                return None;
-            }
-        }
-    }
+    let is_blocked = |count: usize| {
+        let grid = create_grid(all_walls.iter().take(count).cloned().collect());
+        bfs(&start, |point: &Point| neighbors(&grid, point), &is_goal).is_none()
+    };
 
-    None
+    // A count of 0 means the exit is cut off before any byte falls, so no byte is to blame.
+    let count = first_true(0..all_walls.len() + 1, is_blocked)?;
+    let blocking_point = all_walls[count.checked_sub(1)?];
+    Some(format!("{},{}", blocking_point.row, blocking_point.col))
 }
 
 #[cfg(test)]
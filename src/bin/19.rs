@@ -1,9 +1,9 @@
+use advent_of_code::search::Dfs;
 use itertools::Itertools;
 use nom::bytes::complete::tag;
 use nom::character::complete::alpha1;
 use nom::multi::{count, separated_list1};
 use nom::IResult;
-use std::collections::HashMap;
 
 advent_of_code::solution!(19);
 
@@ -27,38 +27,28 @@ fn parse_input(input: &str) -> IResult<&str, Input> {
     ))
 }
 
-fn is_possible(target: &[char], parts: &Vec<String>) -> bool {
-    fn helper<'a>(
-        target: &'a [char],
-        parts: &Vec<String>,
-        memoize: &mut HashMap<&'a [char], bool>,
-    ) -> bool {
-        if memoize.contains_key(target) {
-            return memoize[target];
-        }
-
-        if target.is_empty() {
-            return true;
-        }
-
-        for part in parts {
-            if target.len() >= part.len()
-                && target[..part.len()] == part.chars().collect::<Vec<char>>()
-            {
-                if helper(&target[part.len()..], parts, memoize) {
-                    memoize.insert(target, true);
-                    return true;
-                }
-            }
-        }
-        memoize.insert(target, false);
-        false
-    }
-    helper(target, parts, &mut HashMap::new())
+// States are the number of characters of the target built so far.
+fn towel_search<'a>(
+    target: &'a [char],
+    parts: &'a [Vec<char>],
+) -> Dfs<usize, impl FnMut(&usize) -> Vec<usize> + 'a, impl FnMut(&usize) -> bool + 'a> {
+    let successors = move |&built: &usize| {
+        parts
+            .iter()
+            .filter(move |part| target[built..].starts_with(part))
+            .map(move |part| built + part.len())
+            .collect()
+    };
+    Dfs::new(successors, move |&built| built == target.len()).memoize()
+}
+
+fn is_possible(target: &[char], parts: &[Vec<char>]) -> bool {
+    towel_search(target, parts).find_any(&0).is_some()
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
     let Input { parts, targets } = parse_input(input).unwrap().1;
+    let parts = parts.iter().map(|p| p.chars().collect_vec()).collect_vec();
 
     let result = targets
         .iter()
@@ -68,37 +58,13 @@ pub fn part_one(input: &str) -> Option<u32> {
     Some(result)
 }
 
-fn ways_to_create(target: &[char], parts: &Vec<String>) -> usize {
-    fn helper<'a>(
-        target: &'a [char],
-        parts: &Vec<String>,
-        memoize: &mut HashMap<&'a [char], usize>,
-    ) -> usize {
-        if memoize.contains_key(target) {
-            return memoize[target];
-        }
-
-        if target.is_empty() {
-            return 1;
-        }
-
-        let mut count = 0;
-        for part in parts {
-            if target.len() >= part.len()
-                && target[..part.len()] == part.chars().collect::<Vec<char>>()
-            {
-                count += helper(&target[part.len()..], parts, memoize);
-            }
-        }
-
-        memoize.insert(target, count);
-        count
-    }
-    helper(target, parts, &mut HashMap::new())
+fn ways_to_create(target: &[char], parts: &[Vec<char>]) -> usize {
+    towel_search(target, parts).count_all(&0) as usize
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
     let Input { parts, targets } = parse_input(input).unwrap().1;
+    let parts = parts.iter().map(|p| p.chars().collect_vec()).collect_vec();
 
     let possibles = targets
         .iter()
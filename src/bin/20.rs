@@ -1,6 +1,6 @@
 use advent_of_code::grid::Direction::{E, N, S, W};
 use advent_of_code::grid::{Grid, Point};
-use advent_of_code::search::distance_to_goal;
+use advent_of_code::search::bfs_distance_map;
 use itertools::Itertools;
 use nom::branch::alt;
 use nom::character::complete::{line_ending, none_of};
@@ -17,20 +17,19 @@ fn parse_input(input: &str) -> IResult<&str, Grid<char>> {
     map(many1(parse_line), |data| Grid::new(data))(input)
 }
 
-fn neighbors(grid: &Grid<char>, current: &Point) -> Vec<(Point, u64)> {
+fn neighbors(grid: &Grid<char>, current: &Point) -> Vec<Point> {
     [N, S, E, W]
         .into_iter()
         .map(|d| current.move_direction(&d))
         .filter(|point| grid.in_bounds(point))
         .filter(|point| grid.get(point) != Some('#'))
-        .map(|point| (point, 1))
         .collect_vec()
 }
 
 fn get_distances_to_goal(grid: &Grid<char>) -> HashMap<Point, u64> {
     let end = grid.find(|c| c == 'E').unwrap();
-    let nebs = |point: &Point| neighbors(&grid, point);
-    distance_to_goal(&end, &nebs)
+    let nebs = |point: &Point| neighbors(grid, point);
+    bfs_distance_map([end], nebs).into_distances()
 }
 
 fn count_cheats(g: &Grid<char>, cheat_distance: u32, count_cheats_higher_than: u32) -> u64 {
@@ -43,11 +42,7 @@ fn count_cheats(g: &Grid<char>, cheat_distance: u32, count_cheats_higher_than: u
     grid.set(&start, '.');
     grid.set(&end, '.');
 
-    let free_spots = grid
-        .all_points()
-        .into_iter()
-        .filter(|point| grid.get(point) != Some('#'))
-        .collect_vec();
+    let free_spots = grid.find_all(|c| c != '#').collect_vec();
 
     let mut cheats : HashSet<(Point, Point)> = HashSet::new();
     for entrance in &free_spots {
@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Where the sequence `initial, step(initial), step(step(initial)), ...` starts repeating.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<S> {
+    /// The index of the first state that is part of the cycle.
+    pub start: usize,
+    /// The number of states in the cycle.
+    pub length: usize,
+    /// The state at index `start`.
+    pub state: S,
+}
+
+impl<S> Cycle<S> {
+    /// The index of an earlier state equal to the one at index `n`.
+    pub fn reduce(&self, n: u64) -> usize {
+        let start = self.start as u64;
+        if n < start {
+            n as usize
+        } else {
+            (start + (n - start) % self.length as u64) as usize
+        }
+    }
+}
+
+/// Finds the cycle with Brent's algorithm, which keeps only two states in memory but calls `step`
+/// roughly two to three times per state up to the end of the first loop. The sequence must
+/// eventually repeat. A simulation that can stop, like a guard walking off the map, can be
+/// modelled with an absorbing state such as `None` that steps to itself.
+pub fn detect<S, F>(initial: &S, mut step: F) -> Cycle<S>
+where
+    S: Clone + Eq,
+    F: FnMut(&S) -> S,
+{
+    // Find the length: the hare runs ahead while the tortoise teleports to it at powers of two.
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    // Find the start: with the hare `length` states ahead, they first meet at the cycle start.
+    let mut tortoise = initial.clone();
+    let mut hare = initial.clone();
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle {
+        start,
+        length,
+        state: tortoise,
+    }
+}
+
+/// Finds the cycle by remembering every state, calling `step` exactly once per state up to the
+/// end of the first loop. Faster than `detect` when steps are expensive and states are small.
+pub fn detect_with_hash<S, F>(initial: &S, mut step: F) -> Cycle<S>
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> S,
+{
+    let mut seen: HashMap<S, usize> = HashMap::new();
+    let mut current = initial.clone();
+    for index in 0.. {
+        if let Some(&start) = seen.get(&current) {
+            return Cycle {
+                start,
+                length: index - start,
+                state: current,
+            };
+        }
+        let next = step(&current);
+        seen.insert(current, index);
+        current = next;
+    }
+    unreachable!()
+}
+
+/// The state after `n` steps, skipping over whole loops of the cycle once it is found. Only
+/// simulates up to the end of the first loop, plus the remainder.
+pub fn nth_state<S, F>(initial: &S, mut step: F, n: u64) -> S
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> S,
+{
+    let mut seen: HashMap<S, u64> = HashMap::new();
+    let mut history: Vec<S> = Vec::new();
+    let mut current = initial.clone();
+    for index in 0..n {
+        if let Some(&start) = seen.get(&current) {
+            let cycle = Cycle {
+                start: start as usize,
+                length: (index - start) as usize,
+                state: current,
+            };
+            return history[cycle.reduce(n)].clone();
+        }
+        let next = step(&current);
+        seen.insert(current.clone(), index);
+        history.push(current);
+        current = next;
+    }
+    current
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0 -> 3 -> 6 -> 9 -> 12 -> 5 -> 8 -> 11 -> 4 -> 7 -> 10 -> 3 -> ...
+    fn step(n: &u32) -> u32 {
+        if *n + 3 >= 13 {
+            *n + 3 - 13 + 3
+        } else {
+            n + 3
+        }
+    }
+
+    #[test]
+    fn test_detect() {
+        let expected = Cycle {
+            start: 1,
+            length: 10,
+            state: 3,
+        };
+        assert_eq!(detect(&0, step), expected);
+        assert_eq!(detect_with_hash(&0, step), expected);
+
+        let fixed_point = Cycle {
+            start: 2,
+            length: 1,
+            state: 0,
+        };
+        assert_eq!(detect(&2, |n: &u32| n / 2), fixed_point);
+        assert_eq!(detect_with_hash(&2, |n: &u32| n / 2), fixed_point);
+    }
+
+    #[test]
+    fn test_nth_state() {
+        let mut state = 0;
+        for n in 0..50 {
+            assert_eq!(nth_state(&0, step, n), state);
+            state = step(&state);
+        }
+        assert_eq!(nth_state(&0, step, 1_000_000_000), 10);
+    }
+}
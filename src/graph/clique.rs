@@ -0,0 +1,178 @@
+use super::Graph;
+use std::hash::Hash;
+
+impl<N, E> Graph<N, E>
+where
+    N: Eq + Hash + Clone,
+    E: Clone,
+{
+    /// Every triangle of an undirected graph, as node ids in ascending order. The triangles are
+    /// sorted as well.
+    pub fn triangles(&self) -> Vec<[usize; 3]> {
+        assert!(!self.directed, "triangles are only defined for undirected graphs");
+
+        let mut triangles = Vec::new();
+        for a in self.node_ids() {
+            let mut after_a: Vec<usize> = self.adjacency[a].iter().copied().filter(|&b| b > a).collect();
+            after_a.sort_unstable();
+            for (i, &b) in after_a.iter().enumerate() {
+                for &c in after_a[i + 1..].iter() {
+                    if self.has_edge_id(b, c) {
+                        triangles.push([a, b, c]);
+                    }
+                }
+            }
+        }
+        triangles
+    }
+
+    /// The triangles that `label` is part of, ordered like `triangles`.
+    pub fn triangles_containing(&self, label: &N) -> Vec<[usize; 3]> {
+        assert!(!self.directed, "triangles are only defined for undirected graphs");
+        let Some(a) = self.id(label) else {
+            return Vec::new();
+        };
+
+        let mut neighbors = self.adjacency[a].iter().copied().filter(|&b| b != a).collect::<Vec<_>>();
+        neighbors.sort_unstable();
+        let mut triangles = Vec::new();
+        for (i, &b) in neighbors.iter().enumerate() {
+            for &c in neighbors[i + 1..].iter() {
+                if self.has_edge_id(b, c) {
+                    let mut triangle = [a, b, c];
+                    triangle.sort_unstable();
+                    triangles.push(triangle);
+                }
+            }
+        }
+        triangles.sort_unstable();
+        triangles
+    }
+
+    /// Every maximal clique of an undirected graph, found with Bron–Kerbosch with pivoting. Each
+    /// clique lists its node ids in ascending order, and the cliques are sorted.
+    pub fn maximal_cliques(&self) -> Vec<Vec<usize>> {
+        assert!(!self.directed, "cliques are only defined for undirected graphs");
+
+        let mut cliques = Vec::new();
+        let candidates: Vec<usize> = self.node_ids().collect();
+        self.bron_kerbosch(&mut Vec::new(), candidates, Vec::new(), &mut cliques);
+        for clique in cliques.iter_mut() {
+            clique.sort_unstable();
+        }
+        cliques.sort_unstable();
+        cliques
+    }
+
+    /// The largest clique of an undirected graph. Ties go to the clique that sorts first.
+    pub fn max_clique(&self) -> Vec<usize> {
+        self.maximal_cliques()
+            .into_iter()
+            .rev()
+            .max_by_key(|clique| clique.len())
+            .unwrap_or_default()
+    }
+
+    /// Extends `clique` with every maximal combination of `candidates`, skipping those that could
+    /// also be extended with a node from `excluded`.
+    fn bron_kerbosch(&self, clique: &mut Vec<usize>, mut candidates: Vec<usize>, mut excluded: Vec<usize>, cliques: &mut Vec<Vec<usize>>) {
+        if candidates.is_empty() {
+            if excluded.is_empty() {
+                cliques.push(clique.clone());
+            }
+            return;
+        }
+
+        // Every maximal clique contains the pivot or one of its non-neighbours, so those are the
+        // only candidates worth branching on. The pivot with the most candidate neighbours leaves
+        // the fewest branches.
+        let connected = |a: usize, b: usize| a != b && self.has_edge_id(a, b);
+        let pivot = candidates
+            .iter()
+            .chain(excluded.iter())
+            .copied()
+            .max_by_key(|&p| candidates.iter().filter(|&&c| connected(p, c)).count())
+            .unwrap();
+        let branches: Vec<usize> = candidates.iter().copied().filter(|&c| !connected(pivot, c)).collect();
+
+        for node in branches {
+            clique.push(node);
+            let next_candidates = candidates.iter().copied().filter(|&c| connected(node, c)).collect();
+            let next_excluded = excluded.iter().copied().filter(|&x| connected(node, x)).collect();
+            self.bron_kerbosch(clique, next_candidates, next_excluded, cliques);
+            clique.pop();
+
+            candidates.retain(|&c| c != node);
+            excluded.push(node);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::Graph;
+    use itertools::Itertools;
+
+    const NETWORK: &str = "kh-tc\nqp-kh\nde-cg\nka-co\nyn-aq\nqp-ub\ncg-tb\nvc-aq\ntb-ka\nwh-tc\nyn-cg\nkh-ub\nta-co\nde-co\ntc-td\ntb-wq\nwh-td\nta-ka\ntd-qp\naq-cg\nwq-ub\nub-vc\nde-ta\nwq-aq\nwq-vc\nwh-yn\nka-de\nkh-ta\nco-tc\nwh-qp\ntb-vc\ntd-yn";
+
+    fn labels(graph: &Graph<String>, ids: &[usize]) -> Vec<String> {
+        let mut labels: Vec<String> = ids.iter().map(|&id| graph.label(id).clone()).collect();
+        labels.sort();
+        labels
+    }
+
+    #[test]
+    fn test_triangles() {
+        let graph = Graph::parse_edges(NETWORK, "-", false).unwrap();
+        let triangles = graph.triangles();
+        assert_eq!(triangles.len(), 12);
+        assert!(triangles.windows(2).all(|w| w[0] < w[1]));
+
+        let with_t = triangles
+            .iter()
+            .filter(|t| t.iter().any(|&id| graph.label(id).starts_with('t')))
+            .count();
+        assert_eq!(with_t, 7);
+
+        let co = graph.triangles_containing(&"co".to_string());
+        let co: Vec<Vec<String>> = co.iter().map(|t| labels(&graph, t)).collect();
+        assert_eq!(co.len(), 3);
+        assert!(co.contains(&vec!["co".to_string(), "de".to_string(), "ta".to_string()]));
+    }
+
+    #[test]
+    fn test_max_clique() {
+        let graph = Graph::parse_edges(NETWORK, "-", false).unwrap();
+        assert_eq!(labels(&graph, &graph.max_clique()).join(","), "co,de,ka,ta");
+
+        let cliques = graph.maximal_cliques();
+        assert!(cliques.windows(2).all(|w| w[0] < w[1]));
+        assert!(cliques.iter().all(|c| c.iter().all(|&a| c.iter().all(|&b| a == b || graph.has_edge_id(a, b)))));
+    }
+
+    #[test]
+    fn test_cliques_on_a_larger_graph() {
+        // 500 nodes and about 3000 pseudo-random edges, with a planted clique of 8.
+        let mut state: u64 = 12345;
+        let mut next = || {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (state >> 33) % 500
+        };
+        let mut graph: Graph<u64> = Graph::undirected();
+        for _ in 0..3000 {
+            let (a, b) = (next(), next());
+            if a != b {
+                graph.add_edge(&a, &b, ());
+            }
+        }
+        let planted = [3, 50, 120, 199, 250, 333, 404, 499];
+        for &a in planted.iter() {
+            for &b in planted.iter().filter(|&&b| b != a) {
+                graph.add_edge(&a, &b, ());
+            }
+        }
+
+        let clique: Vec<u64> = graph.max_clique().iter().map(|&id| *graph.label(id)).sorted().collect();
+        assert_eq!(clique, planted.to_vec());
+    }
+}
@@ -0,0 +1,375 @@
+use super::Graph;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+/// A cut splitting the nodes in two, with the edges that cross it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cut<N> {
+    /// The total capacity or weight of the crossing edges. For a max-flow cut this is also the
+    /// value of the maximum flow.
+    pub weight: u64,
+    /// The nodes on one side of the cut: the source side for `max_flow`.
+    pub side: Vec<N>,
+    /// The crossing edges, each oriented from `side` to the other side.
+    pub edges: Vec<(N, N)>,
+}
+
+/// Assigns dense ids to the nodes reachable from `seeds` and collects their edges.
+fn intern<N, I, FN, IN>(seeds: I, mut successors: FN) -> (Vec<N>, Vec<(usize, usize, u64)>)
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, u64)>,
+{
+    let mut ids: HashMap<N, usize> = HashMap::new();
+    let mut labels: Vec<N> = Vec::new();
+    let mut edges = Vec::new();
+    let mut id_of = |node: &N, labels: &mut Vec<N>| {
+        *ids.entry(node.clone()).or_insert_with(|| {
+            labels.push(node.clone());
+            labels.len() - 1
+        })
+    };
+
+    for seed in seeds {
+        id_of(&seed, &mut labels);
+    }
+    let mut next = 0;
+    while next < labels.len() {
+        let node = labels[next].clone();
+        for (to, capacity) in successors(&node) {
+            let to = id_of(&to, &mut labels);
+            edges.push((next, to, capacity));
+        }
+        next += 1;
+    }
+    (labels, edges)
+}
+
+/// A residual network for Dinic's algorithm. Edge `i ^ 1` is the reverse of edge `i`.
+struct FlowNetwork {
+    adjacency: Vec<Vec<usize>>,
+    to: Vec<usize>,
+    capacity: Vec<u64>,
+}
+
+impl FlowNetwork {
+    fn new(nodes: usize) -> Self {
+        FlowNetwork {
+            adjacency: vec![Vec::new(); nodes],
+            to: Vec::new(),
+            capacity: Vec::new(),
+        }
+    }
+
+    fn add_edge(&mut self, from: usize, to: usize, capacity: u64) {
+        self.adjacency[from].push(self.to.len());
+        self.to.push(to);
+        self.capacity.push(capacity);
+        self.adjacency[to].push(self.to.len());
+        self.to.push(from);
+        self.capacity.push(0);
+    }
+
+    /// Distances from `source` along edges with spare capacity, `None` where unreachable.
+    fn levels(&self, source: usize) -> Vec<Option<usize>> {
+        let mut levels = vec![None; self.adjacency.len()];
+        levels[source] = Some(0);
+        let mut queue = VecDeque::from([source]);
+        while let Some(node) = queue.pop_front() {
+            for &edge in self.adjacency[node].iter() {
+                let next = self.to[edge];
+                if self.capacity[edge] > 0 && levels[next].is_none() {
+                    levels[next] = levels[node].map(|l| l + 1);
+                    queue.push_back(next);
+                }
+            }
+        }
+        levels
+    }
+
+    fn max_flow(&mut self, source: usize, sink: usize) -> u64 {
+        let mut total = 0;
+        loop {
+            let mut levels = self.levels(source);
+            if levels[sink].is_none() {
+                return total;
+            }
+
+            // Find a blocking flow with an explicit stack, so long paths cannot overflow.
+            let mut next_edge = vec![0; self.adjacency.len()];
+            let mut path: Vec<usize> = Vec::new();
+            let mut node = source;
+            loop {
+                if node == sink {
+                    let flow = path.iter().map(|&e| self.capacity[e]).min().unwrap();
+                    for &edge in path.iter() {
+                        self.capacity[edge] -= flow;
+                        self.capacity[edge ^ 1] += flow;
+                    }
+                    total += flow;
+                    // Resume from just before the first edge that is now full.
+                    let saturated = path.iter().position(|&e| self.capacity[e] == 0).unwrap();
+                    path.truncate(saturated);
+                    node = path.last().map_or(source, |&e| self.to[e]);
+                    continue;
+                }
+
+                let advance = self.adjacency[node][next_edge[node]..].iter().position(|&edge| {
+                    let next = self.to[edge];
+                    self.capacity[edge] > 0 && levels[next].is_some() && levels[next] == levels[node].map(|l| l + 1)
+                });
+                match advance {
+                    Some(offset) => {
+                        next_edge[node] += offset;
+                        let edge = self.adjacency[node][next_edge[node]];
+                        path.push(edge);
+                        node = self.to[edge];
+                    }
+                    None => {
+                        // Dead end: drop the node from the level graph and back up.
+                        levels[node] = None;
+                        next_edge[node] = self.adjacency[node].len();
+                        let Some(edge) = path.pop() else {
+                            break;
+                        };
+                        node = self.to[edge ^ 1];
+                        next_edge[node] += 1;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The maximum flow from `source` to `sink` with Dinic's algorithm, returned as the minimum cut
+/// that limits it. `successors` gives each node's outgoing edges with their capacities; for an
+/// undirected graph, list every edge from both ends.
+pub fn max_flow<N, FN, IN>(source: &N, sink: &N, successors: FN) -> Cut<N>
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, u64)>,
+{
+    let (labels, edges) = intern([source.clone(), sink.clone()], successors);
+    let mut network = FlowNetwork::new(labels.len());
+    for &(from, to, capacity) in edges.iter() {
+        network.add_edge(from, to, capacity);
+    }
+
+    let weight = if source == sink { 0 } else { network.max_flow(0, 1) };
+
+    // The source side is whatever can still be reached through spare capacity.
+    let levels = network.levels(0);
+    let side: Vec<usize> = (0..labels.len()).filter(|&n| levels[n].is_some()).collect();
+    let cut_edges = edges
+        .iter()
+        .filter(|(from, to, capacity)| *capacity > 0 && levels[*from].is_some() && levels[*to].is_none())
+        .map(|&(from, to, _)| (labels[from].clone(), labels[to].clone()))
+        .collect();
+
+    Cut {
+        weight,
+        side: side.into_iter().map(|n| labels[n].clone()).collect(),
+        edges: cut_edges,
+    }
+}
+
+/// The minimum cut of an undirected graph over all ways to split its nodes in two, with the
+/// Stoer–Wagner algorithm. The graph is made of `nodes` and everything reachable from them;
+/// `successors` gives each node's neighbours with the edge weights, and each edge may be listed
+/// from one or both ends. Returns `None` if there are fewer than two nodes.
+pub fn global_min_cut<N, I, FN, IN>(nodes: I, successors: FN) -> Option<Cut<N>>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, u64)>,
+{
+    let (labels, edge_list) = intern(nodes, successors);
+    if labels.len() < 2 {
+        return None;
+    }
+
+    let mut weights: HashMap<(usize, usize), u64> = HashMap::new();
+    for (a, b, weight) in edge_list {
+        if a != b {
+            weights.insert((a.min(b), a.max(b)), weight);
+        }
+    }
+
+    // Nodes are merged as the algorithm runs; `members` tracks which original nodes each
+    // surviving node stands for.
+    let mut adjacency: Vec<HashMap<usize, u64>> = vec![HashMap::new(); labels.len()];
+    for (&(a, b), &weight) in weights.iter() {
+        adjacency[a].insert(b, weight);
+        adjacency[b].insert(a, weight);
+    }
+    let mut members: Vec<Vec<usize>> = (0..labels.len()).map(|n| vec![n]).collect();
+    let mut active: Vec<usize> = (0..labels.len()).collect();
+    let mut best: Option<(u64, Vec<usize>)> = None;
+
+    while active.len() > 1 {
+        // Maximum adjacency ordering: repeatedly add the node most tightly connected to the
+        // nodes added so far. Ties go to the lowest id.
+        let mut connection: HashMap<usize, u64> = active.iter().map(|&n| (n, 0)).collect();
+        let mut queue: BinaryHeap<(u64, Reverse<usize>)> = active.iter().map(|&n| (0, Reverse(n))).collect();
+        let mut added: HashSet<usize> = HashSet::new();
+        let (mut previous, mut last) = (None, None);
+        while let Some((weight, Reverse(node))) = queue.pop() {
+            if added.contains(&node) || connection[&node] != weight {
+                continue;
+            }
+            added.insert(node);
+            previous = last;
+            last = Some(node);
+            for (&next, &w) in adjacency[node].iter() {
+                if !added.contains(&next) {
+                    let c = connection.get_mut(&next).unwrap();
+                    *c += w;
+                    queue.push((*c, Reverse(next)));
+                }
+            }
+        }
+
+        // The last node's connection is the cut separating it from everything else.
+        let (s, t) = (previous.unwrap(), last.unwrap());
+        let cut_of_phase = connection[&t];
+        if best.as_ref().is_none_or(|(w, _)| cut_of_phase < *w) {
+            best = Some((cut_of_phase, members[t].clone()));
+        }
+
+        // Merge t into s.
+        let t_edges = std::mem::take(&mut adjacency[t]);
+        for (next, w) in t_edges {
+            adjacency[next].remove(&t);
+            if next != s {
+                *adjacency[s].entry(next).or_insert(0) += w;
+                *adjacency[next].entry(s).or_insert(0) += w;
+            }
+        }
+        let t_members = std::mem::take(&mut members[t]);
+        members[s].extend(t_members);
+        active.retain(|&n| n != t);
+    }
+
+    let (weight, mut side) = best.unwrap();
+    side.sort_unstable();
+    let in_side: HashSet<usize> = side.iter().copied().collect();
+    let mut edges: Vec<(usize, usize)> = weights
+        .keys()
+        .filter(|(a, b)| in_side.contains(a) != in_side.contains(b))
+        .map(|&(a, b)| if in_side.contains(&a) { (a, b) } else { (b, a) })
+        .collect();
+    edges.sort_unstable();
+
+    Some(Cut {
+        weight,
+        side: side.into_iter().map(|n| labels[n].clone()).collect(),
+        edges: edges.into_iter().map(|(a, b)| (labels[a].clone(), labels[b].clone())).collect(),
+    })
+}
+
+impl<N, E> Graph<N, E>
+where
+    N: Eq + Hash + Clone,
+    E: Clone,
+{
+    /// `max_flow` over this graph, with each edge's capacity given by `capacity`. In an
+    /// undirected graph every edge can carry flow either way.
+    pub fn max_flow<F>(&self, source: &N, sink: &N, capacity: F) -> Cut<N>
+    where
+        F: Fn(&E) -> u64,
+    {
+        let successors = self.successors();
+        max_flow(source, sink, |n| successors(n).into_iter().map(|(m, e)| (m, capacity(&e))))
+    }
+
+    /// `global_min_cut` over this undirected graph, with each edge's weight given by `weight`.
+    pub fn global_min_cut<F>(&self, weight: F) -> Option<Cut<N>>
+    where
+        F: Fn(&E) -> u64,
+    {
+        assert!(!self.directed, "global_min_cut needs an undirected graph");
+        let successors = self.successors();
+        global_min_cut(self.labels.iter().cloned(), |n| successors(n).into_iter().map(|(m, e)| (m, weight(&e))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_max_flow() {
+        // The network from CLRS, whose maximum flow is 23.
+        let network = |n: &&str| -> Vec<(&str, u64)> {
+            match *n {
+                "s" => vec![("v1", 16), ("v2", 13)],
+                "v1" => vec![("v3", 12)],
+                "v2" => vec![("v1", 4), ("v4", 14)],
+                "v3" => vec![("v2", 9), ("t", 20)],
+                "v4" => vec![("v3", 7), ("t", 4)],
+                _ => vec![],
+            }
+        };
+        let cut = max_flow(&"s", &"t", network);
+        assert_eq!(cut.weight, 23);
+        assert_eq!(cut.side, vec!["s", "v1", "v2", "v4"]);
+        assert_eq!(cut.edges, vec![("v1", "v3"), ("v4", "v3"), ("v4", "t")]);
+
+        let unreachable = max_flow(&"t", &"s", network);
+        assert_eq!(unreachable.weight, 0);
+        assert!(unreachable.edges.is_empty());
+    }
+
+    #[test]
+    fn test_max_flow_on_a_long_path() {
+        let path = |n: &u32| if *n < 200_000 { vec![(n + 1, 3)] } else { vec![] };
+        assert_eq!(max_flow(&0, &200_000, path).weight, 3);
+    }
+
+    const WIRING: &str = "jqt: rhn xhk nvd\nrsh: frs pzl lsr\nxhk: hfx\ncmg: qnr nvd lhk bvb\nrhn: xhk bvb hfx\nbvb: xhk hfx\npzl: lsr hfx nvd\nqnr: nvd\nntq: jqt hfx bvb xhk\nnvd: lhk\nlsr: lhk\nrzs: qnr cmg lsr rsh\nfrs: qnr lhk lsr";
+
+    fn wiring() -> Graph<&'static str> {
+        let mut graph = Graph::undirected();
+        for line in WIRING.lines() {
+            let (from, to) = line.split_once(": ").unwrap();
+            for to in to.split(' ') {
+                graph.add_edge(&from, &to, ());
+            }
+        }
+        graph
+    }
+
+    #[test]
+    fn test_global_min_cut() {
+        let graph = wiring();
+        let cut = graph.global_min_cut(|_| 1).unwrap();
+        assert_eq!(cut.weight, 3);
+        assert_eq!(cut.side.len() * (graph.node_count() - cut.side.len()), 54);
+        let mut wires: Vec<[&str; 2]> = cut
+            .edges
+            .iter()
+            .map(|&(a, b)| if a < b { [a, b] } else { [b, a] })
+            .collect();
+        wires.sort();
+        assert_eq!(wires, vec![["bvb", "cmg"], ["hfx", "pzl"], ["jqt", "nvd"]]);
+
+        assert_eq!(global_min_cut([1], |_: &u32| vec![]), None);
+        let disconnected = global_min_cut([1, 2, 3], |n: &u32| if *n == 1 { vec![(2, 5)] } else { vec![] }).unwrap();
+        assert_eq!(disconnected.weight, 0);
+        assert_eq!(disconnected.side, vec![3]);
+    }
+
+    #[test]
+    fn test_max_flow_matches_min_cut() {
+        let graph = wiring();
+        let cut = graph.max_flow(&"jqt", &"rsh", |_| 1);
+        assert_eq!(cut.weight, 3);
+        assert_eq!(cut.edges.len(), 3);
+        assert!(cut.side.contains(&"jqt") && !cut.side.contains(&"rsh"));
+    }
+}
@@ -0,0 +1,301 @@
+use crate::search::{floyd_warshall, Cost, DistanceMatrix};
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::Range;
+
+mod clique;
+mod flow;
+
+pub use flow::{global_min_cut, max_flow, Cut};
+
+/// A graph given by an explicit list of edges, as opposed to the successor closures used by
+/// `search`.
+///
+/// Node labels (strings, numbers, points) are interned: each distinct label gets a dense id in
+/// `0..node_count()` in the order it was first seen, and the algorithms work on those ids. The
+/// `_id` methods skip the label lookup for callers that already have ids.
+///
+/// In an undirected graph every edge is stored in both directions. Adding an edge that already
+/// exists replaces its weight.
+#[derive(Debug, Clone)]
+pub struct Graph<N, E = ()> {
+    directed: bool,
+    labels: Vec<N>,
+    ids: HashMap<N, usize>,
+    adjacency: Vec<Vec<usize>>,
+    in_degrees: Vec<usize>,
+    weights: HashMap<(usize, usize), E>,
+}
+
+impl<N, E> Graph<N, E>
+where
+    N: Eq + Hash + Clone,
+    E: Clone,
+{
+    pub fn directed() -> Self {
+        Self::new(true)
+    }
+
+    pub fn undirected() -> Self {
+        Self::new(false)
+    }
+
+    fn new(directed: bool) -> Self {
+        Graph {
+            directed,
+            labels: Vec::new(),
+            ids: HashMap::new(),
+            adjacency: Vec::new(),
+            in_degrees: Vec::new(),
+            weights: HashMap::new(),
+        }
+    }
+
+    /// Builds a graph from `(from, to, weight)` triples.
+    pub fn from_edges<I: IntoIterator<Item = (N, N, E)>>(directed: bool, edges: I) -> Self {
+        let mut graph = Self::new(directed);
+        for (from, to, weight) in edges {
+            graph.add_edge(&from, &to, weight);
+        }
+        graph
+    }
+
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    /// Returns the id of `label`, adding it as a node without edges if it is new.
+    pub fn add_node(&mut self, label: &N) -> usize {
+        if let Some(&id) = self.ids.get(label) {
+            return id;
+        }
+        let id = self.labels.len();
+        self.labels.push(label.clone());
+        self.ids.insert(label.clone(), id);
+        self.adjacency.push(Vec::new());
+        self.in_degrees.push(0);
+        id
+    }
+
+    /// Adds an edge, adding either node if it is new.
+    pub fn add_edge(&mut self, from: &N, to: &N, weight: E) {
+        let (from, to) = (self.add_node(from), self.add_node(to));
+        self.add_edge_id(from, to, weight);
+    }
+
+    pub fn add_edge_id(&mut self, from: usize, to: usize, weight: E) {
+        if !self.directed {
+            self.insert_arc(to, from, weight.clone());
+        }
+        self.insert_arc(from, to, weight);
+    }
+
+    fn insert_arc(&mut self, from: usize, to: usize, weight: E) {
+        if self.weights.insert((from, to), weight).is_none() {
+            self.adjacency[from].push(to);
+            self.in_degrees[to] += 1;
+        }
+    }
+
+    pub fn id(&self, label: &N) -> Option<usize> {
+        self.ids.get(label).copied()
+    }
+
+    pub fn label(&self, id: usize) -> &N {
+        &self.labels[id]
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.labels.len()
+    }
+
+    /// The number of edges, counting an undirected edge once.
+    pub fn edge_count(&self) -> usize {
+        if self.directed {
+            self.weights.len()
+        } else {
+            // Self-loops are only stored once.
+            let loops = (0..self.node_count()).filter(|&id| self.weights.contains_key(&(id, id))).count();
+            (self.weights.len() + loops) / 2
+        }
+    }
+
+    /// Node labels in id order.
+    pub fn nodes(&self) -> impl Iterator<Item = &N> + '_ {
+        self.labels.iter()
+    }
+
+    pub fn node_ids(&self) -> Range<usize> {
+        0..self.node_count()
+    }
+
+    /// The nodes an edge leads to from `label`, in the order the edges were added.
+    pub fn neighbors<'a>(&'a self, label: &N) -> impl Iterator<Item = &'a N> + 'a {
+        let ids = self.id(label).map_or(&[][..], |id| self.neighbor_ids(id));
+        ids.iter().map(|&id| &self.labels[id])
+    }
+
+    pub fn neighbor_ids(&self, id: usize) -> &[usize] {
+        &self.adjacency[id]
+    }
+
+    pub fn has_edge(&self, from: &N, to: &N) -> bool {
+        self.edge(from, to).is_some()
+    }
+
+    pub fn has_edge_id(&self, from: usize, to: usize) -> bool {
+        self.weights.contains_key(&(from, to))
+    }
+
+    pub fn edge(&self, from: &N, to: &N) -> Option<&E> {
+        self.edge_id(self.id(from)?, self.id(to)?)
+    }
+
+    pub fn edge_id(&self, from: usize, to: usize) -> Option<&E> {
+        self.weights.get(&(from, to))
+    }
+
+    /// The number of edges leaving `label`, or 0 for an unknown node. For undirected graphs this
+    /// is the number of edges touching it.
+    pub fn degree(&self, label: &N) -> usize {
+        self.id(label).map_or(0, |id| self.adjacency[id].len())
+    }
+
+    /// The number of edges leading into `label`, or 0 for an unknown node.
+    pub fn in_degree(&self, label: &N) -> usize {
+        self.id(label).map_or(0, |id| self.in_degrees[id])
+    }
+
+    /// The graph made of the given nodes and every edge between two of them. Unknown labels are
+    /// ignored, and the remaining nodes keep their relative order.
+    pub fn induced_subgraph<'a, I>(&self, labels: I) -> Graph<N, E>
+    where
+        I: IntoIterator<Item = &'a N>,
+        N: 'a,
+    {
+        let mut keep: Vec<usize> = labels.into_iter().filter_map(|l| self.id(l)).collect();
+        keep.sort_unstable();
+        keep.dedup();
+
+        let mut subgraph = Self::new(self.directed);
+        for &id in keep.iter() {
+            subgraph.add_node(&self.labels[id]);
+        }
+        for &from in keep.iter() {
+            for &to in self.adjacency[from].iter() {
+                if let Some(new_to) = subgraph.id(&self.labels[to]) {
+                    let new_from = subgraph.ids[&self.labels[from]];
+                    subgraph.insert_arc(new_from, new_to, self.weights[&(from, to)].clone());
+                }
+            }
+        }
+        subgraph
+    }
+
+    /// The graph as a successor closure over labels, for `search::dijkstra` and friends.
+    pub fn successors(&self) -> impl Fn(&N) -> Vec<(N, E)> + '_ {
+        move |label| match self.id(label) {
+            Some(from) => self.adjacency[from]
+                .iter()
+                .map(|&to| (self.labels[to].clone(), self.weights[&(from, to)].clone()))
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// The graph as a successor closure over labels where every edge costs 1, whatever its
+    /// weight. This is how an unweighted `Graph<N, ()>` is searched with `search::dijkstra`.
+    pub fn successors_unit(&self) -> impl Fn(&N) -> Vec<(N, u64)> + '_ {
+        move |label| self.neighbors(label).map(|to| (to.clone(), 1)).collect()
+    }
+
+    /// The graph as a successor closure over labels without weights, for `search::bfs`,
+    /// `search::reachable` and the other unweighted searches.
+    pub fn unweighted_successors(&self) -> impl Fn(&N) -> Vec<N> + '_ {
+        move |label| self.neighbors(label).cloned().collect()
+    }
+
+    /// Distances between every pair of nodes with `search::floyd_warshall`, with each edge's
+    /// length given by `weight`. Matrix indices are node ids.
+    pub fn floyd_warshall<C, F>(&self, weight: F) -> DistanceMatrix<N, C>
+    where
+        C: Cost,
+        F: Fn(&E) -> C,
+    {
+        let successors = self.successors();
+        floyd_warshall(self.labels.iter().cloned(), |n| successors(n).into_iter().map(|(m, e)| (m, weight(&e))))
+    }
+
+    /// The graph as a successor closure over ids, which avoids cloning labels.
+    pub fn id_successors(&self) -> impl Fn(&usize) -> Vec<(usize, E)> + '_ {
+        move |&from| self.adjacency[from].iter().map(|&to| (to, self.weights[&(from, to)].clone())).collect()
+    }
+}
+
+impl Graph<String> {
+    /// Parses one unweighted edge per line, such as `kh-tc` with `separator` `"-"`. Blank lines are
+    /// skipped.
+    pub fn parse_edges(input: &str, separator: &str, directed: bool) -> Result<Self, String> {
+        let mut graph = Self::new(directed);
+        for line in input.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            let (from, to) = line
+                .split_once(separator)
+                .ok_or_else(|| format!("expected an edge like a{}b, got {:?}", separator, line))?;
+            graph.add_edge(&from.to_string(), &to.to_string(), ());
+        }
+        Ok(graph)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::{bfs, dijkstra, reachable};
+
+    #[test]
+    fn test_undirected_graph() {
+        let graph = Graph::parse_edges("kh-tc\nqp-kh\nde-cg\nka-co\nyn-aq\nqp-ub\ncg-tb\nkh-tc", "-", false).unwrap();
+        assert!(!graph.is_directed());
+        assert_eq!(graph.node_count(), 11);
+        assert_eq!(graph.edge_count(), 7);
+        let kh = "kh".to_string();
+        assert_eq!(graph.degree(&kh), 2);
+        assert_eq!(graph.in_degree(&kh), 2);
+        assert_eq!(graph.neighbors(&kh).collect::<Vec<_>>(), vec!["tc", "qp"]);
+        assert!(graph.has_edge(&"tc".to_string(), &kh));
+        assert_eq!(graph.neighbors(&"zz".to_string()).count(), 0);
+        assert_eq!(graph.label(graph.id(&kh).unwrap()), &kh);
+
+        let labels = ["qp", "kh", "ub", "de"].map(String::from);
+        let subgraph = graph.induced_subgraph(labels.iter());
+        assert_eq!(subgraph.nodes().collect::<Vec<_>>(), vec!["kh", "qp", "de", "ub"]);
+        assert_eq!(subgraph.edge_count(), 2);
+        assert_eq!(subgraph.degree(&"de".to_string()), 0);
+
+        assert!(Graph::parse_edges("kh tc", "-", false).is_err());
+
+        let (tc, ub) = ("tc".to_string(), "ub".to_string());
+        assert_eq!(dijkstra(&tc, graph.successors_unit(), |n| *n == ub), Some((ub.clone(), 3)));
+        assert_eq!(bfs(&tc, graph.unweighted_successors(), |n| *n == ub).unwrap().path(), vec!["tc", "kh", "qp", "ub"]);
+        assert_eq!(reachable(&"de".to_string(), graph.unweighted_successors()).len(), 3);
+    }
+
+    #[test]
+    fn test_directed_graph_with_dijkstra() {
+        let graph = Graph::from_edges(true, [("a", "b", 10u64), ("a", "c", 1), ("c", "b", 1), ("b", "d", 1), ("c", "d", 7)]);
+        assert_eq!(graph.edge_count(), 5);
+        assert_eq!(graph.degree(&"c"), 2);
+        assert_eq!(graph.in_degree(&"c"), 1);
+        assert_eq!(graph.edge(&"a", &"c"), Some(&1));
+        assert_eq!(graph.edge(&"c", &"a"), None);
+
+        assert_eq!(dijkstra(&"a", graph.successors(), |n| *n == "d"), Some(("d", 3)));
+        assert_eq!(dijkstra(&"d", graph.successors(), |n| *n == "a"), None);
+        let d = graph.id(&"d").unwrap();
+        assert_eq!(dijkstra(&0, graph.id_successors(), |&n| n == d), Some((d, 3)));
+
+        let matrix = graph.floyd_warshall(|&w| w);
+        assert_eq!(matrix[(0, d)], Some(3));
+        assert_eq!(matrix.path(&"a", &"d"), Some(vec!["a", "c", "b", "d"]));
+    }
+}
@@ -1,7 +1,6 @@
 use std::collections::HashMap;
+use std::hash::Hash;
 use itertools::Itertools;
-use std::iter::once;
-use num_traits::ToPrimitive;
 use crate::grid::Direction::{N, E, S, W, NE, SE, NW, SW};
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone, Hash, Ord, PartialOrd)]
@@ -57,6 +56,111 @@ impl Point {
     pub fn manhattan_distance(&self, point: &Point) -> u32 {
         self.row.abs_diff(point.row) + self.col.abs_diff(point.col)
     }
+
+    pub fn chebyshev_distance(&self, point: &Point) -> u32 {
+        self.row.abs_diff(point.row).max(self.col.abs_diff(point.col))
+    }
+}
+
+/// An axis-aligned rectangle of points. Both corners are inclusive, and `min` is never below or
+/// right of `max`, so every rect holds at least one point. The only exception is the `bounds()`
+/// of an empty grid, which contains nothing.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+pub struct Rect {
+    min: Point,
+    max: Point,
+}
+
+impl Rect {
+    pub fn new(min: Point, max: Point) -> Self {
+        assert!(min.row <= max.row && min.col <= max.col, "{:?} is not above and left of {:?}", min, max);
+        Self { min, max }
+    }
+
+    /// The top-left corner.
+    pub fn min(&self) -> Point {
+        self.min
+    }
+
+    /// The bottom-right corner.
+    pub fn max(&self) -> Point {
+        self.max
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max.row - self.min.row + 1) as usize
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max.col - self.min.col + 1) as usize
+    }
+
+    /// The smallest rect containing every point, or `None` if there are no points.
+    pub fn from_points<'a, I>(points: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = &'a Point>,
+    {
+        points.into_iter().fold(None, |rect: Option<Rect>, p| match rect {
+            None => Some(Rect { min: *p, max: *p }),
+            Some(r) => Some(r.union(&Rect { min: *p, max: *p })),
+        })
+    }
+
+    pub fn area(&self) -> usize {
+        self.height() * self.width()
+    }
+
+    pub fn contains(&self, point: &Point) -> bool {
+        point.row >= self.min.row
+            && point.row <= self.max.row
+            && point.col >= self.min.col
+            && point.col <= self.max.col
+    }
+
+    pub fn intersect(&self, other: &Rect) -> Option<Rect> {
+        let min = Point::new(self.min.row.max(other.min.row), self.min.col.max(other.min.col));
+        let max = Point::new(self.max.row.min(other.max.row), self.max.col.min(other.max.col));
+        (min.row <= max.row && min.col <= max.col).then_some(Rect { min, max })
+    }
+
+    /// The smallest rect containing both rects.
+    pub fn union(&self, other: &Rect) -> Rect {
+        Rect {
+            min: Point::new(self.min.row.min(other.min.row), self.min.col.min(other.min.col)),
+            max: Point::new(self.max.row.max(other.max.row), self.max.col.max(other.max.col)),
+        }
+    }
+
+    /// Grows the rect by `n` on every side. A negative `n` shrinks it, and returns `None` if
+    /// nothing is left.
+    pub fn expand(&self, n: i32) -> Option<Rect> {
+        let min = Point::new(self.min.row - n, self.min.col - n);
+        let max = Point::new(self.max.row + n, self.max.col + n);
+        (min.row <= max.row && min.col <= max.col).then_some(Rect { min, max })
+    }
+
+    /// Iterates over every point in the rect in row-major order.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        (self.min.row..=self.max.row)
+            .cartesian_product(self.min.col..=self.max.col)
+            .map(|(r, c)| Point::new(r, c))
+    }
+
+    /// Splits the rect into its NW, NE, SW and SE quadrants. When a side has odd length, the
+    /// middle row or column belongs to no quadrant.
+    pub fn quadrants(&self) -> [Rect; 4] {
+        assert!(self.height() >= 2 && self.width() >= 2, "{:?} is too small to split", self);
+
+        let (half_height, half_width) = (self.height() as i32 / 2, self.width() as i32 / 2);
+        let top = (self.min.row, self.min.row + half_height - 1);
+        let bottom = (self.max.row - half_height + 1, self.max.row);
+        let left = (self.min.col, self.min.col + half_width - 1);
+        let right = (self.max.col - half_width + 1, self.max.col);
+
+        [(top, left), (top, right), (bottom, left), (bottom, right)].map(|((r0, r1), (c0, c1))| {
+            Rect::new(Point::new(r0, c0), Point::new(r1, c1))
+        })
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -83,9 +187,9 @@ where
         let width = input[0].len();
 
         let mut data = HashMap::with_capacity(width * height);
-        for r in 0..height {
-            for c in 0..width {
-                data.insert(Point::new(r as i32, c as i32), input[r][c].clone());
+        for (r, row) in input.iter().enumerate() {
+            for (c, value) in row.iter().enumerate() {
+                data.insert(Point::new(r as i32, c as i32), *value);
             }
         }
 
@@ -107,21 +211,89 @@ where
     }
 
     pub fn in_bounds(&self, point: &Point) -> bool {
-        let Point { row, col } = point;
-        *row >= 0 && *row < self.height.to_i32().unwrap() && *col >= 0 && *col < self.width.to_i32().unwrap()
+        self.bounds().contains(point)
+    }
+
+    /// The rect covering every point of the grid. For an empty grid this rect contains nothing.
+    pub fn bounds(&self) -> Rect {
+        Rect {
+            min: Point::new(0, 0),
+            max: Point::new(self.height as i32 - 1, self.width as i32 - 1),
+        }
+    }
+
+    /// Borrows the part of the grid covered by `rect` without copying it.
+    pub fn view(&self, rect: &Rect) -> GridView<'_, T> {
+        assert!(
+            self.bounds().intersect(rect) == Some(*rect),
+            "{:?} does not fit inside a {}x{} grid",
+            rect,
+            self.height,
+            self.width
+        );
+        GridView { grid: self, rect: *rect }
     }
 
     pub fn all_points(&self) -> Vec<Point> {
+        self.points().collect()
+    }
+
+    /// Iterates over every point in the grid in row-major order without allocating.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
         (0..self.height as i32)
             .cartesian_product(0..self.width as i32)
             .map(|(r, c)| Point { row: r, col: c })
-            .collect()
     }
 
-    pub fn find(&self, predicate: fn(T) -> bool) -> Option<Point> {
-        self.all_points().into_iter().find(|p| {
-            self.get(&p).map_or(false, predicate)
-        })
+    /// Iterates over every populated point and its value in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> + '_ {
+        self.points()
+            .filter_map(|p| self.data.get(&p).map(|value| (p, value)))
+    }
+
+    /// Iterates mutably over every populated point and its value. The order is unspecified.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Point, &mut T)> + '_ {
+        self.data.iter_mut().map(|(p, value)| (*p, value))
+    }
+
+    pub fn find<F>(&self, mut predicate: F) -> Option<Point>
+    where
+        F: FnMut(T) -> bool,
+    {
+        self.iter().find(|(_, &value)| predicate(value)).map(|(p, _)| p)
+    }
+
+    /// Iterates over every point whose value matches the predicate, in row-major order.
+    pub fn find_all<'a, F>(&'a self, mut predicate: F) -> impl Iterator<Item = Point> + 'a
+    where
+        F: FnMut(T) -> bool + 'a,
+    {
+        self.iter().filter(move |(_, &value)| predicate(value)).map(|(p, _)| p)
+    }
+
+    pub fn positions_of(&self, value: T) -> impl Iterator<Item = Point> + '_ {
+        self.find_all(move |v| v == value)
+    }
+
+    pub fn count(&self, value: T) -> usize {
+        self.data.values().filter(|&&v| v == value).count()
+    }
+
+    fn from_fn<F>(width: usize, height: usize, mut f: F) -> Self
+    where
+        F: FnMut(Point) -> T,
+    {
+        let mut data = HashMap::with_capacity(width * height);
+        for (r, c) in (0..height as i32).cartesian_product(0..width as i32) {
+            let point = Point::new(r, c);
+            data.insert(point, f(point));
+        }
+
+        Grid {
+            width,
+            height,
+            data,
+        }
     }
 
     pub fn expand_grid<F>(&self, expand_fn: F) -> Self
@@ -132,19 +304,26 @@ where
             return Grid::new_empty(0, 0);
         }
 
-        // Assuming all tiles have same dimensions after expansion
-        let first_point = self.data.keys().next().unwrap();
-        let first_tile = expand_fn(self.get(first_point).unwrap());
+        // Every tile must match the dimensions of the tile produced for the top-left cell.
+        let first_tile = expand_fn(self.get(&Point::new(0, 0)).unwrap());
         let tile_height = first_tile.len();
-        let tile_width = first_tile[0].len();
+        let tile_width = first_tile.first().map_or(0, |row| row.len());
+        assert!(tile_height > 0 && tile_width > 0, "expanded tiles must not be empty");
 
         let expanded_height = self.height * tile_height;
         let expanded_width = self.width * tile_width;
 
-        let mut expanded_data = HashMap::new();
+        let mut expanded_data = HashMap::with_capacity(expanded_width * expanded_height);
 
         for (point, &value) in self.data.iter() {
             let expanded_tile = expand_fn(value);
+            assert!(
+                expanded_tile.len() == tile_height && expanded_tile.iter().all(|row| row.len() == tile_width),
+                "tile for {:?} is not {}x{}",
+                point,
+                tile_height,
+                tile_width
+            );
 
             for (tile_row_index, tile_row) in expanded_tile.iter().enumerate() {
                 for (tile_col_index, &tile_value) in tile_row.iter().enumerate() {
@@ -163,32 +342,339 @@ where
         }
     }
 
+    /// Repeats the whole grid `n_rows` times vertically and `n_cols` times horizontally.
+    pub fn tile(&self, n_rows: usize, n_cols: usize) -> Self {
+        self.tile_with(n_rows, n_cols, |_, _, value| value)
+    }
+
+    /// Like `tile`, but passes each value through `f` together with the (row, col) index of the
+    /// copy it lands in. Useful for maps where every repetition shifts its values.
+    pub fn tile_with<F>(&self, n_rows: usize, n_cols: usize, f: F) -> Self
+    where
+        F: Fn(usize, usize, T) -> T,
+    {
+        assert!(n_rows > 0 && n_cols > 0, "cannot tile a grid 0 times");
+
+        let (height, width) = (self.height as i32, self.width as i32);
+        Grid::from_fn(self.width * n_cols, self.height * n_rows, |p| {
+            let source = Point::new(p.row % height, p.col % width);
+            let tile_row = (p.row / height) as usize;
+            let tile_col = (p.col / width) as usize;
+            f(tile_row, tile_col, self.get(&source).unwrap())
+        })
+    }
+
+    /// Turns every cell into a `k`x`k` block of the same value.
+    pub fn scale(&self, k: usize) -> Self {
+        assert!(k > 0, "scale factor must be positive");
+        let k = k as i32;
+        Grid::from_fn(self.width * k as usize, self.height * k as usize, |p| {
+            self.get(&Point::new(p.row / k, p.col / k)).unwrap()
+        })
+    }
+
+    /// The inverse of `scale`: collapses every `k`x`k` block into one cell. The reducer receives
+    /// the block's values in row-major order.
+    pub fn shrink<F>(&self, k: usize, reducer: F) -> Self
+    where
+        F: Fn(&[T]) -> T,
+    {
+        assert!(k > 0, "shrink factor must be positive");
+        assert!(
+            self.height.is_multiple_of(k) && self.width.is_multiple_of(k),
+            "a {}x{} grid cannot be shrunk by {}",
+            self.height,
+            self.width,
+            k
+        );
+
+        let k = k as i32;
+        let mut block = Vec::with_capacity((k * k) as usize);
+        Grid::from_fn(self.width / k as usize, self.height / k as usize, |p| {
+            block.clear();
+            for (r, c) in (0..k).cartesian_product(0..k) {
+                block.push(self.get(&Point::new(p.row * k + r, p.col * k + c)).unwrap());
+            }
+            reducer(&block)
+        })
+    }
+
+    /// Surrounds the grid with a `border` cells wide frame of `value`.
+    pub fn pad(&self, border: usize, value: T) -> Self {
+        let offset = border as i32;
+        Grid::from_fn(self.width + 2 * border, self.height + 2 * border, |p| {
+            self.get(&Point::new(p.row - offset, p.col - offset))
+                .unwrap_or(value)
+        })
+    }
+
+    /// Copies out the part of the grid covered by `rect`. The rect's top-left corner becomes
+    /// (0, 0) in the result.
+    pub fn crop(&self, rect: &Rect) -> Self {
+        self.view(rect).to_grid()
+    }
+
     pub fn neighbors(&self, point: &Point) -> Vec<Point> {
-        let dirs = vec![N, E, S, W, NE, NW, SE, SW];
-        dirs.iter().map(|d| point.move_direction(d))
+        self.neighbors_iter(point).collect()
+    }
+
+    /// Iterates over the in-bounds points in all eight directions around `point`.
+    pub fn neighbors_iter<'a>(&'a self, point: &Point) -> impl Iterator<Item = Point> + 'a {
+        let point = *point;
+        [N, E, S, W, NE, NW, SE, SW]
+            .into_iter()
+            .map(move |d| point.move_direction(&d))
             .filter(|p| self.in_bounds(p))
-            .collect()
     }
 
     pub fn transform(&self, f: &dyn Fn(&Grid<T>, &Point) -> T) -> Grid<T> {
         let mut result = self.clone();
-        self.all_points().iter().for_each(|point| {
-            let position = f(&self, &point);
+        self.points().for_each(|point| {
+            let position = f(self, &point);
             result.set(&point, position);
         });
         result
     }
+}
 
-    pub fn to_string(&self) -> String {
-        let mut result = String::new();
+impl<T> std::fmt::Display for Grid<T>
+where
+    T: std::fmt::Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for row in 0..self.height {
             for col in 0..self.width {
                 let point = Point::new(row as i32, col as i32);
-                let tile = self.get(&point).unwrap();
-                result.push_str(&tile.to_string());
+                write!(f, "{}", self.data[&point])?;
             }
-            result.push('\n');
+            writeln!(f)?;
         }
-        result
+        Ok(())
+    }
+}
+
+/// A borrowed window onto part of a `Grid`. Points are relative to the top-left corner of the
+/// window, so (0, 0) is `rect.min` in the underlying grid.
+#[derive(Debug, Clone, Copy)]
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    rect: Rect,
+}
+
+impl<'a, T> GridView<'a, T>
+where
+    T: Default + Clone + PartialEq + Copy + std::fmt::Display,
+{
+    pub fn width(&self) -> usize {
+        self.rect.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.rect.height()
+    }
+
+    /// The area of the underlying grid that this view covers.
+    pub fn rect(&self) -> Rect {
+        self.rect
+    }
+
+    pub fn get(&self, point: &Point) -> Option<T> {
+        let in_view = point.row >= 0
+            && point.col >= 0
+            && (point.row as usize) < self.height()
+            && (point.col as usize) < self.width();
+        in_view.then(|| self.grid.get(&self.rect.min.add(point))).flatten()
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        (0..self.height() as i32)
+            .cartesian_product(0..self.width() as i32)
+            .map(|(r, c)| Point::new(r, c))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &'a T)> + 'a {
+        let GridView { grid, rect } = *self;
+        rect.points()
+            .filter_map(move |p| grid.data.get(&p).map(|value| (p.subtract(&rect.min), value)))
+    }
+
+    pub fn to_grid(&self) -> Grid<T> {
+        Grid::from_fn(self.width(), self.height(), |p| self.get(&p).unwrap())
+    }
+}
+
+impl<T> Grid<T>
+where
+    T: Default + Clone + PartialEq + Copy + std::fmt::Display + Eq + Hash,
+{
+    /// Counts how many times each value occurs in the grid.
+    pub fn histogram(&self) -> HashMap<T, usize> {
+        let mut counts = HashMap::new();
+        for value in self.data.values() {
+            *counts.entry(*value).or_insert(0) += 1;
+        }
+        counts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(rows: &[&str]) -> Grid<char> {
+        Grid::new(rows.iter().map(|r| r.chars().collect()).collect())
+    }
+
+    #[test]
+    fn test_points_and_iter() {
+        let g = grid(&["ab", "cd", "ef"]);
+        assert_eq!(g.points().collect::<Vec<_>>(), g.all_points());
+        assert_eq!(g.points().count(), 6);
+        assert_eq!(g.points().nth(3), Some(Point::new(1, 1)));
+        assert_eq!(g.iter().map(|(_, &c)| c).collect::<String>(), "abcdef");
+        assert!(g.iter().all(|(p, &c)| g.get(&p) == Some(c)));
+        assert_eq!(Grid::<char>::new_empty(0, 0).points().count(), 0);
+    }
+
+    #[test]
+    fn test_iter_mut() {
+        let mut g = grid(&["ab", "cd"]);
+        for (p, c) in g.iter_mut() {
+            if p.row == 1 {
+                *c = c.to_ascii_uppercase();
+            }
+        }
+        assert_eq!(g.to_string(), "ab\nCD\n");
+    }
+
+    #[test]
+    fn test_find_and_find_all() {
+        let g = grid(&["a.b", "..a", "a.."]);
+        assert_eq!(g.find(|c| c == 'a'), Some(Point::new(0, 0)));
+        assert_eq!(g.find(|c| c == 'z'), None);
+        let letters: Vec<Point> = g.find_all(|c| c.is_alphabetic()).collect();
+        assert_eq!(letters, vec![Point::new(0, 0), Point::new(0, 2), Point::new(1, 2), Point::new(2, 0)]);
+
+        // Closures may capture and mutate state.
+        let mut seen = 0;
+        assert_eq!(g.find(|c| {
+            seen += 1;
+            c == 'b'
+        }), Some(Point::new(0, 2)));
+        assert_eq!(seen, 3);
+    }
+
+    #[test]
+    fn test_positions_count_and_histogram() {
+        let g = grid(&["a.b", "..a", "a.."]);
+        assert_eq!(g.positions_of('a').collect::<Vec<_>>(), vec![Point::new(0, 0), Point::new(1, 2), Point::new(2, 0)]);
+        assert_eq!(g.positions_of('z').count(), 0);
+        assert_eq!(g.count('a'), 3);
+        assert_eq!(g.count('.'), 5);
+        assert_eq!(g.count('z'), 0);
+        assert_eq!(g.histogram(), HashMap::from([('a', 3), ('b', 1), ('.', 5)]));
+    }
+
+    #[test]
+    fn test_neighbors_iter() {
+        let g = grid(&["abc", "def", "ghi"]);
+        let around = |p: Point| g.neighbors_iter(&p).map(|q| g.get(&q).unwrap()).sorted().collect::<String>();
+        assert_eq!(around(Point::new(1, 1)), "abcdfghi");
+        // Corners and edges only yield the neighbours inside the grid.
+        assert_eq!(around(Point::new(0, 0)), "bde");
+        assert_eq!(around(Point::new(2, 2)), "efh");
+        assert_eq!(around(Point::new(0, 1)), "acdef");
+        assert_eq!(around(Point::new(1, 2)), "bcehi");
+        // A point outside the grid still gets its in-bounds neighbours.
+        assert_eq!(around(Point::new(-1, -1)), "a");
+        assert_eq!(g.neighbors_iter(&Point::new(1, 1)).collect::<Vec<_>>(), g.neighbors(&Point::new(1, 1)));
+
+        let single = grid(&["x"]);
+        assert_eq!(single.neighbors_iter(&Point::new(0, 0)).count(), 0);
+    }
+
+    #[test]
+    fn test_tile() {
+        let g = grid(&["ab", "cd"]);
+        assert_eq!(g.tile(2, 3).to_string(), "ababab\ncdcdcd\nababab\ncdcdcd\n");
+    }
+
+    #[test]
+    fn test_tile_with() {
+        let g = Grid::new(vec![vec![1u8, 2]]);
+        let tiled = g.tile_with(2, 2, |r, c, v| v + (r + c) as u8);
+        assert_eq!(tiled.to_string(), "1223\n2334\n");
+    }
+
+    #[test]
+    fn test_scale_and_shrink() {
+        let g = grid(&["ab", "cd"]);
+        let scaled = g.scale(2);
+        assert_eq!(scaled.to_string(), "aabb\naabb\nccdd\nccdd\n");
+        assert_eq!(scaled.shrink(2, |block| block[0]), g);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_shrink_rejects_uneven_blocks() {
+        grid(&["abc", "def"]).shrink(2, |block| block[0]);
+    }
+
+    #[test]
+    fn test_pad_and_crop() {
+        let g = grid(&["ab", "cd"]);
+        let padded = g.pad(1, '.');
+        assert_eq!(padded.to_string(), "....\n.ab.\n.cd.\n....\n");
+        assert_eq!(padded.crop(&Rect::new(Point::new(1, 1), Point::new(2, 2))), g);
+    }
+
+    #[test]
+    fn test_view() {
+        let g = grid(&["abc", "def", "ghi"]);
+        let view = g.view(&Rect::new(Point::new(1, 1), Point::new(2, 2)));
+        assert_eq!(view.get(&Point::new(0, 0)), Some('e'));
+        assert_eq!(view.get(&Point::new(0, 2)), None);
+        assert_eq!(view.iter().map(|(_, &c)| c).collect::<String>(), "efhi");
+    }
+
+    #[test]
+    fn test_rect_set_operations() {
+        let a = Rect::new(Point::new(0, 0), Point::new(3, 3));
+        let b = Rect::new(Point::new(2, 1), Point::new(5, 2));
+        assert_eq!(a.intersect(&b), Some(Rect::new(Point::new(2, 1), Point::new(3, 2))));
+        assert_eq!(a.union(&b), Rect::new(Point::new(0, 0), Point::new(5, 3)));
+        assert_eq!(a.intersect(&Rect::new(Point::new(4, 4), Point::new(5, 5))), None);
+        assert_eq!(a.area(), 16);
+        assert_eq!(a.expand(1).map(|r| r.area()), Some(36));
+        assert_eq!(a.expand(0), Some(a));
+        assert_eq!(a.expand(-1), Some(Rect::new(Point::new(1, 1), Point::new(2, 2))));
+        assert_eq!(a.expand(-2), None);
+        assert_eq!(b.expand(-1), None);
+        assert_eq!(a.expand(1).unwrap().min(), Point::new(-1, -1));
+        assert_eq!(b.points().count(), b.area());
+    }
+
+    #[test]
+    fn test_rect_from_points() {
+        let points = [Point::new(3, -1), Point::new(0, 4), Point::new(2, 2)];
+        assert_eq!(Rect::from_points(&points), Some(Rect::new(Point::new(0, -1), Point::new(3, 4))));
+        assert_eq!(Rect::from_points(&[]), None);
+    }
+
+    #[test]
+    fn test_rect_quadrants_skip_middle() {
+        let rect = Rect::new(Point::new(0, 0), Point::new(6, 10));
+        let [nw, ne, sw, se] = rect.quadrants();
+        assert_eq!(nw, Rect::new(Point::new(0, 0), Point::new(2, 4)));
+        assert_eq!(ne, Rect::new(Point::new(0, 6), Point::new(2, 10)));
+        assert_eq!(sw, Rect::new(Point::new(4, 0), Point::new(6, 4)));
+        assert_eq!(se, Rect::new(Point::new(4, 6), Point::new(6, 10)));
+        assert!(!rect.quadrants().iter().any(|q| q.contains(&Point::new(3, 5))));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_expand_grid_rejects_mismatched_tiles() {
+        grid(&["ab"]).expand_grid(|c| if c == 'a' { vec![vec![c, c]] } else { vec![vec![c]] });
     }
 }
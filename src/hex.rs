@@ -0,0 +1,300 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// A hex tile in axial coordinates. The implied third cube coordinate is `s = -q - r`.
+///
+/// Axial coordinates do not depend on how the hexagons are drawn, so the same point type serves
+/// both pointy-top and flat-top maps. Only the names of the six directions differ; see
+/// `PointyDirection` and `FlatDirection`.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash, Ord, PartialOrd)]
+pub struct HexPoint {
+    pub q: i32,
+    pub r: i32,
+}
+
+/// The six neighbours of a pointy-top hexagon, which has flat sides to the east and west.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash, Ord, PartialOrd)]
+pub enum PointyDirection {
+    E, NE, NW, W, SW, SE
+}
+
+/// The six neighbours of a flat-top hexagon, which has flat sides to the north and south.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash, Ord, PartialOrd)]
+pub enum FlatDirection {
+    N, NE, SE, S, SW, NW
+}
+
+pub trait HexDirection {
+    /// The axial offset of one step in this direction.
+    fn offset(&self) -> HexPoint;
+}
+
+impl PointyDirection {
+    pub const ALL: [PointyDirection; 6] = [
+        PointyDirection::E,
+        PointyDirection::NE,
+        PointyDirection::NW,
+        PointyDirection::W,
+        PointyDirection::SW,
+        PointyDirection::SE,
+    ];
+}
+
+impl FlatDirection {
+    pub const ALL: [FlatDirection; 6] = [
+        FlatDirection::N,
+        FlatDirection::NE,
+        FlatDirection::SE,
+        FlatDirection::S,
+        FlatDirection::SW,
+        FlatDirection::NW,
+    ];
+}
+
+impl HexDirection for PointyDirection {
+    fn offset(&self) -> HexPoint {
+        match self {
+            PointyDirection::E => HexPoint::new(1, 0),
+            PointyDirection::NE => HexPoint::new(1, -1),
+            PointyDirection::NW => HexPoint::new(0, -1),
+            PointyDirection::W => HexPoint::new(-1, 0),
+            PointyDirection::SW => HexPoint::new(-1, 1),
+            PointyDirection::SE => HexPoint::new(0, 1),
+        }
+    }
+}
+
+impl HexDirection for FlatDirection {
+    fn offset(&self) -> HexPoint {
+        match self {
+            FlatDirection::N => HexPoint::new(0, -1),
+            FlatDirection::NE => HexPoint::new(1, -1),
+            FlatDirection::SE => HexPoint::new(1, 0),
+            FlatDirection::S => HexPoint::new(0, 1),
+            FlatDirection::SW => HexPoint::new(-1, 1),
+            FlatDirection::NW => HexPoint::new(-1, 0),
+        }
+    }
+}
+
+impl FromStr for PointyDirection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "e" => Ok(PointyDirection::E),
+            "ne" => Ok(PointyDirection::NE),
+            "nw" => Ok(PointyDirection::NW),
+            "w" => Ok(PointyDirection::W),
+            "sw" => Ok(PointyDirection::SW),
+            "se" => Ok(PointyDirection::SE),
+            _ => Err(format!("invalid pointy-top direction {:?}", s)),
+        }
+    }
+}
+
+impl FromStr for FlatDirection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "n" => Ok(FlatDirection::N),
+            "ne" => Ok(FlatDirection::NE),
+            "se" => Ok(FlatDirection::SE),
+            "s" => Ok(FlatDirection::S),
+            "sw" => Ok(FlatDirection::SW),
+            "nw" => Ok(FlatDirection::NW),
+            _ => Err(format!("invalid flat-top direction {:?}", s)),
+        }
+    }
+}
+
+impl HexPoint {
+    pub fn new(q: i32, r: i32) -> Self {
+        Self { q, r }
+    }
+
+    pub fn origin() -> Self {
+        Self::new(0, 0)
+    }
+
+    /// The third cube coordinate.
+    pub fn s(&self) -> i32 {
+        -self.q - self.r
+    }
+
+    pub fn add(&self, point: &HexPoint) -> HexPoint {
+        HexPoint::new(self.q + point.q, self.r + point.r)
+    }
+
+    pub fn subtract(&self, point: &HexPoint) -> HexPoint {
+        HexPoint::new(self.q - point.q, self.r - point.r)
+    }
+
+    pub fn scale(&self, k: i32) -> HexPoint {
+        HexPoint::new(self.q * k, self.r * k)
+    }
+
+    pub fn move_direction<D: HexDirection>(&self, dir: &D) -> HexPoint {
+        self.add(&dir.offset())
+    }
+
+    pub fn move_directions<'a, D, I>(&self, directions: I) -> Vec<HexPoint>
+    where
+        D: HexDirection + 'a,
+        I: IntoIterator<Item = &'a D>,
+    {
+        directions
+            .into_iter()
+            .scan(*self, |p, d| {
+                *p = p.move_direction(d);
+                Some(*p)
+            })
+            .collect()
+    }
+
+    /// The six adjacent tiles. The order is the same for both layouts.
+    pub fn neighbors(&self) -> [HexPoint; 6] {
+        PointyDirection::ALL.map(|d| self.move_direction(&d))
+    }
+
+    /// The number of steps between two tiles.
+    pub fn distance(&self, point: &HexPoint) -> u32 {
+        let diff = self.subtract(point);
+        (diff.q.unsigned_abs() + diff.r.unsigned_abs() + diff.s().unsigned_abs()) / 2
+    }
+
+    /// Every tile exactly `radius` steps away, walking once around the ring.
+    pub fn ring(&self, radius: u32) -> Vec<HexPoint> {
+        if radius == 0 {
+            return vec![*self];
+        }
+
+        // Start at the south-west corner and walk each of the six sides in turn.
+        let mut current = self.add(&PointyDirection::SW.offset().scale(radius as i32));
+        let mut result = Vec::with_capacity(6 * radius as usize);
+        for dir in PointyDirection::ALL {
+            for _ in 0..radius {
+                result.push(current);
+                current = current.move_direction(&dir);
+            }
+        }
+        result
+    }
+
+    /// Every tile at most `radius` steps away, ordered from the centre outwards ring by ring.
+    pub fn spiral(&self, radius: u32) -> Vec<HexPoint> {
+        (0..=radius).flat_map(|k| self.ring(k)).collect()
+    }
+}
+
+/// A sparse map of hex tiles. Unlike `Grid`, there is no fixed extent: `set` adds tiles as needed.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct HexGrid<T> {
+    data: HashMap<HexPoint, T>,
+}
+
+impl<T> HexGrid<T>
+where
+    T: Clone + PartialEq + Copy,
+{
+    pub fn new() -> Self {
+        HexGrid {
+            data: HashMap::new(),
+        }
+    }
+
+    /// A hexagon-shaped map of every tile within `radius` steps of the origin.
+    pub fn hexagon(radius: u32, value: T) -> Self {
+        HexGrid {
+            data: HexPoint::origin()
+                .spiral(radius)
+                .into_iter()
+                .map(|p| (p, value))
+                .collect(),
+        }
+    }
+
+    pub fn get(&self, point: &HexPoint) -> Option<T> {
+        self.data.get(point).copied()
+    }
+
+    pub fn set(&mut self, point: &HexPoint, value: T) {
+        self.data.insert(*point, value);
+    }
+
+    pub fn remove(&mut self, point: &HexPoint) -> Option<T> {
+        self.data.remove(point)
+    }
+
+    pub fn contains(&self, point: &HexPoint) -> bool {
+        self.data.contains_key(point)
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Iterates over the tiles and their values. The order is unspecified.
+    pub fn iter(&self) -> impl Iterator<Item = (HexPoint, &T)> + '_ {
+        self.data.iter().map(|(p, value)| (*p, value))
+    }
+
+    /// Iterates over the neighbours of `point` that are present in the grid.
+    pub fn neighbors<'a>(&'a self, point: &HexPoint) -> impl Iterator<Item = HexPoint> + 'a {
+        point.neighbors().into_iter().filter(|p| self.contains(p))
+    }
+
+    pub fn count(&self, value: T) -> usize {
+        self.data.values().filter(|&&v| v == value).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::dijkstra;
+
+    #[test]
+    fn test_flat_top_walk() {
+        let dirs: Vec<FlatDirection> = "ne,ne,s,s".split(',').map(|d| d.parse().unwrap()).collect();
+        let end = *HexPoint::origin().move_directions(&dirs).last().unwrap();
+        assert_eq!(HexPoint::origin().distance(&end), 2);
+    }
+
+    #[test]
+    fn test_pointy_top_walk() {
+        let dirs: Vec<PointyDirection> = ["nw", "w", "sw", "e", "e"].iter().map(|d| d.parse().unwrap()).collect();
+        let end = *HexPoint::origin().move_directions(&dirs).last().unwrap();
+        assert_eq!(end, HexPoint::origin());
+    }
+
+    #[test]
+    fn test_ring_and_spiral() {
+        let center = HexPoint::new(2, -1);
+        let ring = center.ring(3);
+        assert_eq!(ring.len(), 18);
+        assert!(ring.iter().all(|p| p.distance(&center) == 3));
+        assert_eq!(center.spiral(2).len(), 19);
+    }
+
+    #[test]
+    fn test_dijkstra_on_hex_grid() {
+        let mut grid = HexGrid::hexagon(3, '.');
+        grid.set(&HexPoint::new(1, 0), '#');
+        grid.set(&HexPoint::new(1, -1), '#');
+        let goal = HexPoint::new(3, -1);
+        let successors = |p: &HexPoint| {
+            grid.neighbors(p)
+                .filter(|n| grid.get(n) == Some('.'))
+                .map(|n| (n, 1))
+                .collect::<Vec<_>>()
+        };
+        let (_, cost) = dijkstra(&HexPoint::origin(), successors, |p| *p == goal).unwrap();
+        assert_eq!(cost, 4);
+    }
+}
@@ -0,0 +1,205 @@
+use crate::grid::{Direction, Grid, Point};
+use std::collections::HashMap;
+use std::fmt::Display;
+
+/// Identifies an entity placed on a `LayeredGrid`. Ids stay valid while the entity moves.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash, Ord, PartialOrd)]
+pub struct EntityId(usize);
+
+/// A map split into an immutable terrain layer and an entity layer on top of it.
+///
+/// Walls and floor live in the terrain, while things that move around (robots, guards, boxes)
+/// are entities. Each entity can be found by id or by position in O(1), and at most one entity
+/// occupies a point at a time.
+#[derive(Debug, Clone)]
+pub struct LayeredGrid<T, E> {
+    terrain: Grid<T>,
+    entities: HashMap<EntityId, (Point, E)>,
+    occupied: HashMap<Point, EntityId>,
+    next_id: usize,
+}
+
+impl<T, E> LayeredGrid<T, E>
+where
+    T: Default + Clone + PartialEq + Copy + Display,
+{
+    pub fn new(terrain: Grid<T>) -> Self {
+        LayeredGrid {
+            terrain,
+            entities: HashMap::new(),
+            occupied: HashMap::new(),
+            next_id: 0,
+        }
+    }
+
+    pub fn terrain(&self) -> &Grid<T> {
+        &self.terrain
+    }
+
+    pub fn terrain_at(&self, point: &Point) -> Option<T> {
+        self.terrain.get(point)
+    }
+
+    /// Places a new entity. Returns `None` if the point is outside the terrain or already taken.
+    pub fn add(&mut self, point: &Point, entity: E) -> Option<EntityId> {
+        if !self.terrain.in_bounds(point) || self.occupied.contains_key(point) {
+            return None;
+        }
+
+        let id = EntityId(self.next_id);
+        self.next_id += 1;
+        self.entities.insert(id, (*point, entity));
+        self.occupied.insert(*point, id);
+        Some(id)
+    }
+
+    pub fn remove(&mut self, id: EntityId) -> Option<(Point, E)> {
+        let (point, entity) = self.entities.remove(&id)?;
+        self.occupied.remove(&point);
+        Some((point, entity))
+    }
+
+    pub fn position(&self, id: EntityId) -> Option<Point> {
+        self.entities.get(&id).map(|(p, _)| *p)
+    }
+
+    pub fn entity(&self, id: EntityId) -> Option<&E> {
+        self.entities.get(&id).map(|(_, e)| e)
+    }
+
+    pub fn entity_at(&self, point: &Point) -> Option<(EntityId, &E)> {
+        let id = *self.occupied.get(point)?;
+        Some((id, &self.entities[&id].1))
+    }
+
+    pub fn is_occupied(&self, point: &Point) -> bool {
+        self.occupied.contains_key(point)
+    }
+
+    /// Moves an entity to `to`, updating both the id and the position lookup. Returns `false`
+    /// and leaves the grid untouched if the destination is outside the terrain or occupied by a
+    /// different entity.
+    pub fn move_entity(&mut self, id: EntityId, to: &Point) -> bool {
+        let Some((from, _)) = self.entities.get(&id) else {
+            return false;
+        };
+        let from = *from;
+        if !self.terrain.in_bounds(to) || self.occupied.get(to).is_some_and(|&other| other != id) {
+            return false;
+        }
+
+        self.occupied.remove(&from);
+        self.occupied.insert(*to, id);
+        self.entities.get_mut(&id).unwrap().0 = *to;
+        true
+    }
+
+    pub fn move_direction(&mut self, id: EntityId, dir: &Direction) -> bool {
+        match self.position(id) {
+            Some(point) => self.move_entity(id, &point.move_direction(dir)),
+            None => false,
+        }
+    }
+
+    /// Iterates over every entity with its id and position. The order is unspecified.
+    pub fn entities(&self) -> impl Iterator<Item = (EntityId, Point, &E)> + '_ {
+        self.entities.iter().map(|(id, (p, e))| (*id, *p, e))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+}
+
+impl<T> LayeredGrid<T, T>
+where
+    T: Default + Clone + PartialEq + Copy + Display,
+{
+    /// Splits a single-layer map: every value matching `is_entity` becomes an entity and the
+    /// terrain underneath it is replaced with `floor`.
+    pub fn from_grid<F>(grid: &Grid<T>, is_entity: F, floor: T) -> Self
+    where
+        F: Fn(T) -> bool,
+    {
+        let mut terrain = grid.clone();
+        let entities = grid.find_all(&is_entity).collect::<Vec<_>>();
+        for point in entities.iter() {
+            terrain.set(point, floor);
+        }
+
+        let mut layered = LayeredGrid::new(terrain);
+        for point in entities {
+            layered.add(&point, grid.get(&point).unwrap());
+        }
+        layered
+    }
+
+    /// Combines both layers into a single map, with entities drawn over the terrain.
+    pub fn flatten(&self) -> Grid<T> {
+        let mut result = self.terrain.clone();
+        for (_, point, entity) in self.entities() {
+            result.set(&point, *entity);
+        }
+        result
+    }
+}
+
+impl<T, E> Display for LayeredGrid<T, E>
+where
+    T: Default + Clone + PartialEq + Copy + Display,
+    E: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for point in self.terrain.points() {
+            match self.entity_at(&point) {
+                Some((_, entity)) => write!(f, "{}", entity)?,
+                None => write!(f, "{}", self.terrain.get(&point).unwrap())?,
+            }
+            if point.col as usize == self.terrain.width - 1 {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(rows: &[&str]) -> Grid<char> {
+        Grid::new(rows.iter().map(|r| r.chars().collect()).collect())
+    }
+
+    #[test]
+    fn test_from_grid_separates_layers() {
+        let grid = parse(&["#.@", "O.#"]);
+        let layered = LayeredGrid::from_grid(&grid, |c| c == '@' || c == 'O', '.');
+        assert_eq!(layered.terrain().to_string(), "#..\n..#\n");
+        assert_eq!(layered.len(), 2);
+        assert_eq!(layered.entity_at(&Point::new(0, 2)).map(|(_, &c)| c), Some('@'));
+        assert_eq!(layered.to_string(), "#.@\nO.#\n");
+        assert_eq!(layered.flatten(), grid);
+    }
+
+    #[test]
+    fn test_moves_update_both_layers() {
+        let mut layered = LayeredGrid::new(parse(&["...", "..."]));
+        let robot = layered.add(&Point::new(0, 0), 'R').unwrap();
+        let crate_id = layered.add(&Point::new(0, 1), 'C').unwrap();
+
+        assert!(!layered.move_direction(robot, &Direction::E));
+        assert!(layered.move_direction(robot, &Direction::S));
+        assert_eq!(layered.position(robot), Some(Point::new(1, 0)));
+        assert!(!layered.is_occupied(&Point::new(0, 0)));
+        assert_eq!(layered.entity_at(&Point::new(1, 0)).map(|(id, _)| id), Some(robot));
+
+        assert!(!layered.move_entity(crate_id, &Point::new(5, 5)));
+        assert_eq!(layered.remove(crate_id), Some((Point::new(0, 1), 'C')));
+        assert_eq!(layered.to_string(), "...\nR..\n");
+    }
+}
@@ -1,3 +1,8 @@
 pub mod template;
 pub mod search;
-pub mod grid;
\ No newline at end of file
+pub mod grid;
+pub mod hex;
+pub mod layered;
+pub mod union_find;
+pub mod cycle;
+pub mod graph;
@@ -25,6 +25,7 @@ mod args {
             day: Day,
             release: bool,
             dhat: bool,
+            stats: bool,
             submit: Option<u8>,
         },
         All {
@@ -71,6 +72,7 @@ mod args {
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                stats: args.contains("--stats"),
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
@@ -114,8 +116,9 @@ fn main() {
                 day,
                 release,
                 dhat,
+                stats,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+            } => solve::handle(day, release, dhat, stats, submit),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
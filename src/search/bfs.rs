@@ -0,0 +1,174 @@
+use super::{DistanceMap, Exploration, SearchResult, Visitor};
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+/// Breadth-first search for graphs where every edge costs 1. `successors` returns the
+/// neighbouring nodes without costs, and the result keeps the predecessors needed to recover the
+/// path. Unlike `dijkstra`, nodes do not need to be `Ord`.
+pub fn bfs<N, FN, IN, FS>(start: &N, successors: FN, is_goal: FS) -> Option<SearchResult<N>>
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+    FS: FnMut(&N) -> bool,
+{
+    bfs_with_visitor(start, successors, is_goal, &mut ())
+}
+
+/// Like `bfs`, reporting every step of the search to `visitor`.
+pub fn bfs_with_visitor<N, FN, IN, FS, V>(start: &N, successors: FN, is_goal: FS, visitor: &mut V) -> Option<SearchResult<N>>
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+    FS: FnMut(&N) -> bool,
+    V: Visitor<N, u64>,
+{
+    breadth_first(vec![start.clone()], successors, is_goal, visitor).into_result()
+}
+
+/// The number of steps from the nearest source to every reachable node, for graphs where every
+/// edge costs 1.
+pub fn bfs_distance_map<N, I, FN, IN>(sources: I, successors: FN) -> DistanceMap<N>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+{
+    let exploration = breadth_first(sources.into_iter().collect(), successors, |_| false, &mut ());
+    DistanceMap {
+        distances: exploration.settled,
+        predecessors: exploration.predecessors,
+    }
+}
+
+fn breadth_first<N, FN, IN, FS, V>(starts: Vec<N>, mut successors: FN, mut is_goal: FS, visitor: &mut V) -> Exploration<N, u64>
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+    FS: FnMut(&N) -> bool,
+    V: Visitor<N, u64>,
+{
+    // In a breadth-first search a node's distance is final as soon as it is discovered.
+    let mut settled: HashMap<N, u64> = HashMap::new();
+    let mut predecessors: HashMap<N, N> = HashMap::new();
+    let mut queue: VecDeque<(N, u64)> = VecDeque::new();
+
+    for start in starts {
+        if let Entry::Vacant(entry) = settled.entry(start.clone()) {
+            entry.insert(0);
+            visitor.on_push(&start, 0);
+            queue.push_back((start, 0));
+        }
+    }
+
+    while let Some((current, current_dist)) = queue.pop_front() {
+        visitor.on_pop(&current, current_dist);
+        visitor.on_settle(&current, current_dist);
+
+        if is_goal(&current) {
+            visitor.on_finish();
+            return Exploration {
+                goal: Some((current, current_dist)),
+                settled,
+                predecessors,
+            };
+        }
+
+        for neighbor in successors(&current) {
+            if let Entry::Vacant(entry) = settled.entry(neighbor.clone()) {
+                entry.insert(current_dist + 1);
+                predecessors.insert(neighbor.clone(), current.clone());
+                visitor.on_relax(&current, &neighbor, current_dist + 1);
+                visitor.on_push(&neighbor, current_dist + 1);
+                queue.push_back((neighbor, current_dist + 1));
+            }
+        }
+    }
+
+    visitor.on_finish();
+    Exploration {
+        goal: None,
+        settled,
+        predecessors,
+    }
+}
+
+/// Shortest paths for graphs whose edges cost either 0 or 1, using a deque instead of a heap:
+/// free edges go to the front of the queue and unit edges to the back. Any other edge cost
+/// panics.
+pub fn bfs_01<N, FN, IN, FS>(start: &N, successors: FN, is_goal: FS) -> Option<SearchResult<N>>
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, u64)>,
+    FS: FnMut(&N) -> bool,
+{
+    bfs_01_with_visitor(start, successors, is_goal, &mut ())
+}
+
+/// Like `bfs_01`, reporting every step of the search to `visitor`.
+pub fn bfs_01_with_visitor<N, FN, IN, FS, V>(
+    start: &N,
+    mut successors: FN,
+    mut is_goal: FS,
+    visitor: &mut V,
+) -> Option<SearchResult<N>>
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, u64)>,
+    FS: FnMut(&N) -> bool,
+    V: Visitor<N, u64>,
+{
+    let mut distances: HashMap<N, u64> = HashMap::new();
+    let mut settled: HashMap<N, u64> = HashMap::new();
+    let mut predecessors: HashMap<N, N> = HashMap::new();
+    let mut queue: VecDeque<(N, u64)> = VecDeque::new();
+
+    distances.insert(start.clone(), 0);
+    visitor.on_push(start, 0);
+    queue.push_back((start.clone(), 0));
+
+    while let Some((current, current_dist)) = queue.pop_front() {
+        visitor.on_pop(&current, current_dist);
+        if settled.contains_key(&current) {
+            continue;
+        }
+        visitor.on_settle(&current, current_dist);
+        settled.insert(current.clone(), current_dist);
+
+        if is_goal(&current) {
+            visitor.on_finish();
+            return Some(SearchResult {
+                goal: current,
+                cost: current_dist,
+                settled,
+                predecessors,
+            });
+        }
+
+        for (neighbor, cost) in successors(&current) {
+            assert!(cost <= 1, "bfs_01 only supports edge costs of 0 and 1, got {}", cost);
+
+            let new_dist = current_dist + cost;
+            if distances.get(&neighbor).is_none_or(|&best| new_dist < best) {
+                distances.insert(neighbor.clone(), new_dist);
+                predecessors.insert(neighbor.clone(), current.clone());
+                visitor.on_relax(&current, &neighbor, new_dist);
+                visitor.on_push(&neighbor, new_dist);
+                if cost == 0 {
+                    queue.push_front((neighbor, new_dist));
+                } else {
+                    queue.push_back((neighbor, new_dist));
+                }
+            }
+        }
+    }
+
+    visitor.on_finish();
+    None
+}
@@ -0,0 +1,310 @@
+use super::{Cost, Visitor};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::hash::Hash;
+
+/// One half of a bidirectional Dijkstra search.
+struct Side<N, C> {
+    distances: HashMap<N, C>,
+    predecessors: HashMap<N, N>,
+    settled: HashSet<N>,
+    queue: BinaryHeap<(Reverse<C>, N)>,
+}
+
+impl<N, C> Side<N, C>
+where
+    N: Eq + Hash + Clone + Ord,
+    C: Cost,
+{
+    fn new(start: &N) -> Self {
+        Side {
+            distances: HashMap::from([(start.clone(), C::zero())]),
+            predecessors: HashMap::new(),
+            settled: HashSet::new(),
+            queue: BinaryHeap::from([(Reverse(C::zero()), start.clone())]),
+        }
+    }
+
+    /// The smallest distance still on the frontier, after dropping stale entries.
+    fn peek<V: Visitor<N, C>>(&mut self, visitor: &mut V) -> Option<C> {
+        while let Some((Reverse(dist), node)) = self.queue.peek() {
+            if self.settled.contains(node) || self.distances.get(node).is_some_and(|best| dist > best) {
+                visitor.on_pop(node, *dist);
+                self.queue.pop();
+            } else {
+                return Some(*dist);
+            }
+        }
+        None
+    }
+
+    /// The route from `node` back to this side's start, both included.
+    fn path_from(&self, node: &N) -> Vec<N> {
+        let mut path = vec![node.clone()];
+        while let Some(previous) = self.predecessors.get(path.last().unwrap()) {
+            path.push(previous.clone());
+        }
+        path
+    }
+}
+
+/// Dijkstra from both ends at once, for graphs where every edge can be walked both ways at the
+/// same cost. In a big open area each side only covers a disc of about half the radius, so far
+/// fewer nodes are expanded than by `dijkstra`. Returns the path from `start` to `goal` with its
+/// cost.
+pub fn bidirectional_dijkstra<N, C, FN, IN>(start: &N, goal: &N, successors: FN) -> Option<(Vec<N>, C)>
+where
+    N: Eq + Hash + Clone + Ord,
+    C: Cost,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+{
+    bidirectional_dijkstra_with_visitor(start, goal, successors, &mut ())
+}
+
+/// Like `bidirectional_dijkstra`, reporting every step of both searches to `visitor`. Costs are
+/// measured from whichever end the node was reached from.
+pub fn bidirectional_dijkstra_with_visitor<N, C, FN, IN, V>(start: &N, goal: &N, mut successors: FN, visitor: &mut V) -> Option<(Vec<N>, C)>
+where
+    N: Eq + Hash + Clone + Ord,
+    C: Cost,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    V: Visitor<N, C>,
+{
+    let mut forward: Side<N, C> = Side::new(start);
+    let mut backward = Side::new(goal);
+    visitor.on_push(start, C::zero());
+    visitor.on_push(goal, C::zero());
+    // The cheapest complete route so far, as its cost and the node where the two halves meet.
+    let mut best: Option<(C, N)> = (start == goal).then(|| (C::zero(), start.clone()));
+
+    while let (Some(f), Some(b)) = (forward.peek(visitor), backward.peek(visitor)) {
+        // Any route not seen yet leaves both frontiers, so it costs at least this much.
+        if best.as_ref().is_some_and(|(cost, _)| f + b >= *cost) {
+            break;
+        }
+
+        let (side, other) = if f <= b { (&mut forward, &backward) } else { (&mut backward, &forward) };
+        let (Reverse(current_dist), current) = side.queue.pop().unwrap();
+        visitor.on_pop(&current, current_dist);
+        visitor.on_settle(&current, current_dist);
+        side.settled.insert(current.clone());
+
+        for (neighbor, cost) in successors(&current) {
+            let new_dist = current_dist + cost;
+            if side.distances.get(&neighbor).is_none_or(|&known| new_dist < known) {
+                side.distances.insert(neighbor.clone(), new_dist);
+                side.predecessors.insert(neighbor.clone(), current.clone());
+                visitor.on_relax(&current, &neighbor, new_dist);
+                visitor.on_push(&neighbor, new_dist);
+                side.queue.push((Reverse(new_dist), neighbor.clone()));
+
+                if let Some(&rest) = other.distances.get(&neighbor) {
+                    let total = new_dist + rest;
+                    if best.as_ref().is_none_or(|(cost, _)| total < *cost) {
+                        best = Some((total, neighbor));
+                    }
+                }
+            }
+        }
+    }
+
+    visitor.on_finish();
+    let (cost, meeting) = best?;
+    let mut path = forward.path_from(&meeting);
+    path.reverse();
+    path.extend(backward.path_from(&meeting).into_iter().skip(1));
+    Some((path, cost))
+}
+
+/// Breadth-first search from both ends at once, for unweighted graphs whose edges can be walked
+/// both ways. Each round grows the smaller frontier by a whole level. Returns the path from
+/// `start` to `goal`, both included.
+pub fn bidirectional_bfs<N, FN, IN>(start: &N, goal: &N, successors: FN) -> Option<Vec<N>>
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+{
+    bidirectional_bfs_with_visitor(start, goal, successors, &mut ())
+}
+
+/// Like `bidirectional_bfs`, reporting every step of both searches to `visitor`. Costs are the
+/// number of steps from whichever end the node was reached from.
+pub fn bidirectional_bfs_with_visitor<N, FN, IN, V>(start: &N, goal: &N, mut successors: FN, visitor: &mut V) -> Option<Vec<N>>
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+    V: Visitor<N, u64>,
+{
+    if start == goal {
+        visitor.on_finish();
+        return Some(vec![start.clone()]);
+    }
+
+    // Per side: every node seen with its predecessor, the nodes of the newest level and its depth.
+    let mut seen: [HashMap<N, Option<N>>; 2] = [HashMap::from([(start.clone(), None)]), HashMap::from([(goal.clone(), None)])];
+    let mut frontiers: [Vec<N>; 2] = [vec![start.clone()], vec![goal.clone()]];
+    let mut depths: [u64; 2] = [0, 0];
+    visitor.on_push(start, 0);
+    visitor.on_push(goal, 0);
+
+    while !frontiers[0].is_empty() && !frontiers[1].is_empty() {
+        let side = if frontiers[0].len() <= frontiers[1].len() { 0 } else { 1 };
+        let other = 1 - side;
+        let depth = depths[side];
+        depths[side] += 1;
+
+        // The other side may have seen the nodes of this level at different depths, so finish the
+        // level and keep the meeting point closest to its start.
+        let mut meeting: Option<(usize, N)> = None;
+        let mut next_frontier = Vec::new();
+        for current in std::mem::take(&mut frontiers[side]) {
+            visitor.on_pop(&current, depth);
+            visitor.on_settle(&current, depth);
+            for neighbor in successors(&current) {
+                if seen[side].contains_key(&neighbor) {
+                    continue;
+                }
+                seen[side].insert(neighbor.clone(), Some(current.clone()));
+                visitor.on_relax(&current, &neighbor, depth + 1);
+                visitor.on_push(&neighbor, depth + 1);
+                if seen[other].contains_key(&neighbor) {
+                    let rest = walk_back(&seen[other], &neighbor).len();
+                    if meeting.as_ref().is_none_or(|(best, _)| rest < *best) {
+                        meeting = Some((rest, neighbor.clone()));
+                    }
+                }
+                next_frontier.push(neighbor);
+            }
+        }
+        frontiers[side] = next_frontier;
+
+        if let Some((_, meeting)) = meeting {
+            visitor.on_finish();
+            let mut path = walk_back(&seen[0], &meeting);
+            path.reverse();
+            path.extend(walk_back(&seen[1], &meeting).into_iter().skip(1));
+            return Some(path);
+        }
+    }
+
+    visitor.on_finish();
+    None
+}
+
+fn walk_back<N>(seen: &HashMap<N, Option<N>>, node: &N) -> Vec<N>
+where
+    N: Eq + Hash + Clone,
+{
+    let mut path = vec![node.clone()];
+    while let Some(Some(previous)) = seen.get(path.last().unwrap()) {
+        path.push(previous.clone());
+    }
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Direction::{E, N, S, W};
+    use crate::grid::Point;
+    use crate::search::tests::{open_grid_successors, weighted, weighted_reversed};
+    use crate::search::{dijkstra, SearchStats};
+
+    #[test]
+    fn test_bidirectional_dijkstra() {
+        let (start, goal) = (Point::new(0, 0), Point::new(0, 6));
+        let (path, cost) = bidirectional_dijkstra(&start, &goal, open_grid_successors(7)).unwrap();
+        assert_eq!(cost, 18);
+        assert_eq!(path.len(), 19);
+        assert_eq!((path.first(), path.last()), (Some(&start), Some(&goal)));
+        assert!(path.windows(2).all(|w| w[0].manhattan_distance(&w[1]) == 1));
+
+        assert_eq!(bidirectional_dijkstra(&start, &start, open_grid_successors(7)), Some((vec![start], 0)));
+        assert_eq!(bidirectional_dijkstra(&start, &Point::new(10, 10), open_grid_successors(7)), None);
+
+        // `weighted` made undirected: the detour through 2 still beats the direct edge.
+        let symmetric = |node: &u32| {
+            let mut edges = weighted(node);
+            edges.extend(weighted_reversed(node));
+            edges
+        };
+        assert_eq!(bidirectional_dijkstra(&0, &3, symmetric), Some((vec![0, 2, 1, 3], 3)));
+        assert_eq!(bidirectional_dijkstra(&3, &0, symmetric), Some((vec![3, 1, 2, 0], 3)));
+    }
+
+    #[test]
+    fn test_bidirectional_bfs() {
+        let unit = |p: &Point| open_grid_successors(7)(p).into_iter().map(|(q, _)| q).collect::<Vec<_>>();
+        let (start, goal) = (Point::new(0, 0), Point::new(0, 6));
+        let path = bidirectional_bfs(&start, &goal, unit).unwrap();
+        assert_eq!(path.len(), 19);
+        assert_eq!((path.first(), path.last()), (Some(&start), Some(&goal)));
+        assert!(path.windows(2).all(|w| w[0].manhattan_distance(&w[1]) == 1));
+
+        assert_eq!(bidirectional_bfs(&start, &start, unit), Some(vec![start]));
+        assert_eq!(bidirectional_bfs(&start, &Point::new(10, 10), unit), None);
+    }
+
+    #[test]
+    fn test_bidirectional_search_expands_fewer_nodes() {
+        let open = |point: &Point| {
+            [N, E, S, W]
+                .iter()
+                .map(|d| point.move_direction(d))
+                .filter(|p| p.row >= 0 && p.col >= 0 && p.row < 101 && p.col < 101)
+                .collect::<Vec<_>>()
+        };
+        let (start, goal) = (Point::new(50, 20), Point::new(50, 80));
+
+        let mut one_sided = 0;
+        let counted = |p: &Point| {
+            one_sided += 1;
+            open(p).into_iter().map(|q| (q, 1u64))
+        };
+        assert_eq!(dijkstra(&start, counted, |p| *p == goal), Some((goal, 60)));
+
+        let mut two_sided = 0;
+        let counted = |p: &Point| {
+            two_sided += 1;
+            open(p).into_iter().map(|q| (q, 1u64))
+        };
+        let (path, cost) = bidirectional_dijkstra(&start, &goal, counted).unwrap();
+        assert_eq!((path.len(), cost), (61, 60));
+        assert!(two_sided * 3 < one_sided * 2, "{} vs {} expansions", two_sided, one_sided);
+
+        let mut breadth_first = 0;
+        let counted = |p: &Point| {
+            breadth_first += 1;
+            open(p)
+        };
+        assert_eq!(bidirectional_bfs(&start, &goal, counted).map(|p| p.len()), Some(61));
+        assert!(breadth_first * 3 < one_sided * 2, "{} vs {} expansions", breadth_first, one_sided);
+    }
+
+    #[test]
+    fn test_bidirectional_with_visitor() {
+        let (start, goal) = (Point::new(0, 0), Point::new(0, 6));
+
+        let mut expansions = 0;
+        let counted = |p: &Point| {
+            expansions += 1;
+            open_grid_successors(7)(p)
+        };
+        let mut stats = SearchStats::new("");
+        assert_eq!(bidirectional_dijkstra_with_visitor(&start, &goal, counted, &mut stats).map(|(_, c)| c), Some(18));
+        assert_eq!(stats.expanded, expansions);
+
+        let mut expansions = 0;
+        let counted = |p: &Point| {
+            expansions += 1;
+            open_grid_successors(7)(p).into_iter().map(|(q, _)| q)
+        };
+        let mut stats = SearchStats::new("");
+        assert_eq!(bidirectional_bfs_with_visitor(&start, &goal, counted, &mut stats).map(|p| p.len()), Some(19));
+        assert_eq!(stats.expanded, expansions);
+    }
+}
@@ -0,0 +1,58 @@
+use num_traits::PrimInt;
+use std::ops::Range;
+
+/// The smallest value in `range` for which `predicate` holds, or `None` if it holds for none of
+/// them. `predicate` must be monotone: once true, it stays true for every larger value. Calls
+/// `predicate` about log2(range length) times.
+pub fn first_true<T, F>(range: Range<T>, mut predicate: F) -> Option<T>
+where
+    T: PrimInt,
+    F: FnMut(T) -> bool,
+{
+    let (mut lo, mut hi) = (range.start, range.end);
+    // Invariant: predicate is false below lo, and true from hi on (if hi is inside the range).
+    while lo < hi {
+        // The floor of the mean, without overflowing even when the range spans more than T::MAX.
+        let mid = (lo & hi) + ((lo ^ hi) >> 1);
+        if predicate(mid) {
+            hi = mid;
+        } else {
+            lo = mid + T::one();
+        }
+    }
+    (lo < range.end).then_some(lo)
+}
+
+/// The largest value in `range` for which `predicate` holds, or `None` if it holds for none of
+/// them. `predicate` must be monotone: once false, it stays false for every larger value.
+pub fn last_true<T, F>(range: Range<T>, mut predicate: F) -> Option<T>
+where
+    T: PrimInt,
+    F: FnMut(T) -> bool,
+{
+    let start = range.start;
+    match first_true(range.clone(), |x| !predicate(x)) {
+        Some(first_false) if first_false > start => Some(first_false - T::one()),
+        Some(_) => None,
+        None => (start < range.end).then(|| range.end - T::one()),
+    }
+}
+
+/// Like `first_true` on `lo..`, for when there is no known upper bound: the step size doubles
+/// until `predicate` holds, then the last gap is bisected. Returns `None` if it never holds
+/// before `u64::MAX`.
+pub fn first_true_unbounded<F>(lo: u64, mut predicate: F) -> Option<u64>
+where
+    F: FnMut(u64) -> bool,
+{
+    let mut lo = lo;
+    let mut step = 1u64;
+    loop {
+        let probe = lo.checked_add(step - 1)?;
+        if predicate(probe) {
+            return first_true(lo..probe, &mut predicate).or(Some(probe));
+        }
+        lo = probe.checked_add(1)?;
+        step = step.saturating_mul(2);
+    }
+}
@@ -0,0 +1,102 @@
+use num_traits::Zero;
+use std::cmp::Ordering;
+use std::fmt::Debug;
+use std::ops::Add;
+
+/// The weight of an edge or the length of a path: any `Ord + Copy + Add + Zero` type, which
+/// covers every primitive integer type. Use `Lex` for lexicographic tuple costs and `FloatCost`
+/// for floating-point distances.
+///
+/// The result types default to `u64` costs. Inference cannot see that default, though: an
+/// unsuffixed integer literal in a successor closure still falls back to `i32`, as it does
+/// anywhere else in Rust. Write `1u64` or annotate the closure's return type to get `u64`.
+///
+/// The search functions assume edge costs are never negative, even for signed types.
+pub trait Cost: Ord + Copy + Add<Output = Self> + Zero + Debug {}
+
+impl<C> Cost for C where C: Ord + Copy + Add<Output = C> + Zero + Debug {}
+
+/// A tuple of costs that adds element-wise and compares lexicographically. A `Lex((time, turns))`
+/// cost therefore prefers the fastest route and breaks ties by the fewest turns.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Lex<T>(pub T);
+
+impl<A: Add<Output = A>, B: Add<Output = B>> Add for Lex<(A, B)> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Lex((self.0 .0 + other.0 .0, self.0 .1 + other.0 .1))
+    }
+}
+
+impl<A: Zero, B: Zero> Zero for Lex<(A, B)> {
+    fn zero() -> Self {
+        Lex((A::zero(), B::zero()))
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0 .0.is_zero() && self.0 .1.is_zero()
+    }
+}
+
+impl<A: Add<Output = A>, B: Add<Output = B>, C: Add<Output = C>> Add for Lex<(A, B, C)> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        let (a, b, c) = self.0;
+        let (x, y, z) = other.0;
+        Lex((a + x, b + y, c + z))
+    }
+}
+
+impl<A: Zero, B: Zero, C: Zero> Zero for Lex<(A, B, C)> {
+    fn zero() -> Self {
+        Lex((A::zero(), B::zero(), C::zero()))
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0 .0.is_zero() && self.0 .1.is_zero() && self.0 .2.is_zero()
+    }
+}
+
+/// An `f64` cost. Floats are not `Ord`, so this orders them with `f64::total_cmp`.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct FloatCost(pub f64);
+
+impl PartialEq for FloatCost {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for FloatCost {}
+
+impl PartialOrd for FloatCost {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for FloatCost {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl Add for FloatCost {
+    type Output = FloatCost;
+
+    fn add(self, other: Self) -> Self::Output {
+        FloatCost(self.0 + other.0)
+    }
+}
+
+impl Zero for FloatCost {
+    fn zero() -> Self {
+        FloatCost(0.0)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0 == 0.0
+    }
+}
@@ -0,0 +1,232 @@
+use super::{Cost, Visitor};
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+/// Depth-first backtracking over an implicit tree or DAG of states.
+///
+/// A state is expanded with `successors` until it satisfies `is_goal`; goal states are not
+/// expanded further. `prune` cuts off states that cannot lead anywhere useful, and `memoize`
+/// caches results by state, which only makes sense when the outcome below a state depends on the
+/// state alone. The search uses an explicit stack, so deep state spaces do not overflow.
+///
+/// The closures are stored as they are, so a search costs no more than a hand-written loop.
+pub struct Dfs<N, FN, FG, FP = fn(&N) -> bool, V = ()> {
+    successors: FN,
+    is_goal: FG,
+    prune: Option<FP>,
+    memoize: bool,
+    visitor: V,
+    node: PhantomData<fn(&N)>,
+}
+
+impl<N, FN, IN, FG> Dfs<N, FN, FG>
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+    FG: FnMut(&N) -> bool,
+{
+    pub fn new(successors: FN, is_goal: FG) -> Self {
+        Dfs {
+            successors,
+            is_goal,
+            prune: None,
+            memoize: false,
+            visitor: (),
+            node: PhantomData,
+        }
+    }
+}
+
+impl<N, FN, IN, FG, FP, V> Dfs<N, FN, FG, FP, V>
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+    FG: FnMut(&N) -> bool,
+    FP: FnMut(&N) -> bool,
+    V: Visitor<N, ()>,
+{
+    /// Skips every state for which `prune` returns true, along with everything below it.
+    pub fn prune<FQ>(self, prune: FQ) -> Dfs<N, FN, FG, FQ, V>
+    where
+        FQ: FnMut(&N) -> bool,
+    {
+        Dfs {
+            successors: self.successors,
+            is_goal: self.is_goal,
+            prune: Some(prune),
+            memoize: self.memoize,
+            visitor: self.visitor,
+            node: PhantomData,
+        }
+    }
+
+    /// Remembers the outcome for every state, so states reachable along several routes are only
+    /// explored once. Ignored by `minimize`, where the outcome also depends on the best cost so
+    /// far.
+    pub fn memoize(mut self) -> Self {
+        self.memoize = true;
+        self
+    }
+
+    /// Reports every step of the search to `visitor`, usually a `&mut` to it. A state is pushed
+    /// when it goes on the stack, popped when it comes off, and settled when it survives pruning
+    /// and memoization; each search ends with `on_finish`. The visitor sees `()` for every cost.
+    pub fn visitor<W>(self, visitor: W) -> Dfs<N, FN, FG, FP, W>
+    where
+        W: Visitor<N, ()>,
+    {
+        Dfs {
+            successors: self.successors,
+            is_goal: self.is_goal,
+            prune: self.prune,
+            memoize: self.memoize,
+            visitor,
+            node: PhantomData,
+        }
+    }
+
+    fn is_pruned(&mut self, state: &N) -> bool {
+        self.prune.as_mut().is_some_and(|prune| prune(state))
+    }
+
+    fn push(&mut self, state: N, stack: &mut Vec<N>) {
+        self.visitor.on_push(&state, ());
+        stack.push(state);
+    }
+
+    // Successors are pushed in reverse, so they are explored in the order they were returned.
+    fn push_successors(&mut self, state: &N, stack: &mut Vec<N>) {
+        let successors: Vec<N> = (self.successors)(state).into_iter().collect();
+        for next in successors.into_iter().rev() {
+            self.push(next, stack);
+        }
+    }
+
+    /// Returns the first goal found, or `None` if no goal is reachable.
+    pub fn find_any(&mut self, start: &N) -> Option<N> {
+        let mut explored: HashSet<N> = HashSet::new();
+        let mut stack = Vec::new();
+        self.push(start.clone(), &mut stack);
+
+        while let Some(state) = stack.pop() {
+            self.visitor.on_pop(&state, ());
+            if self.memoize && !explored.insert(state.clone()) {
+                continue;
+            }
+            if self.is_pruned(&state) {
+                continue;
+            }
+            self.visitor.on_settle(&state, ());
+            if (self.is_goal)(&state) {
+                self.visitor.on_finish();
+                return Some(state);
+            }
+            self.push_successors(&state, &mut stack);
+        }
+
+        self.visitor.on_finish();
+        None
+    }
+
+    /// Counts the distinct routes from `start` to a goal. With `memoize`, the state graph must be
+    /// acyclic.
+    pub fn count_all(&mut self, start: &N) -> u128 {
+        if !self.memoize {
+            let mut count = 0;
+            let mut stack = Vec::new();
+            self.push(start.clone(), &mut stack);
+            while let Some(state) = stack.pop() {
+                self.visitor.on_pop(&state, ());
+                if self.is_pruned(&state) {
+                    continue;
+                }
+                self.visitor.on_settle(&state, ());
+                if (self.is_goal)(&state) {
+                    count += 1;
+                    continue;
+                }
+                self.push_successors(&state, &mut stack);
+            }
+            self.visitor.on_finish();
+            return count;
+        }
+
+        // Post-order walk: a state is counted once all of its successors have been.
+        let mut counts: HashMap<N, u128> = HashMap::new();
+        self.visitor.on_push(start, ());
+        let mut stack: Vec<(N, Option<Vec<N>>)> = vec![(start.clone(), None)];
+        while let Some((state, successors)) = stack.pop() {
+            if let Some(successors) = successors {
+                let count = successors
+                    .iter()
+                    .map(|s| counts.get(s).copied().expect("count_all with memoize needs an acyclic state graph"))
+                    .sum();
+                counts.insert(state, count);
+                continue;
+            }
+            self.visitor.on_pop(&state, ());
+            if counts.contains_key(&state) {
+                continue;
+            }
+            if self.is_pruned(&state) {
+                counts.insert(state, 0);
+                continue;
+            }
+            self.visitor.on_settle(&state, ());
+            if (self.is_goal)(&state) {
+                counts.insert(state, 1);
+                continue;
+            }
+
+            let successors: Vec<N> = (self.successors)(&state).into_iter().collect();
+            let pending = successors.iter().filter(|s| !counts.contains_key(s)).cloned().collect::<Vec<_>>();
+            for next in pending.iter() {
+                self.visitor.on_push(next, ());
+            }
+            stack.push((state, Some(successors)));
+            stack.extend(pending.into_iter().map(|s| (s, None)));
+        }
+
+        self.visitor.on_finish();
+        counts[start]
+    }
+
+    /// Branch and bound: finds the goal with the lowest `cost`. `lower_bound` must never exceed
+    /// the cost of any goal below a state; subtrees that cannot beat the best goal found so far
+    /// are skipped. Pass `|_| 0` to search without a bound.
+    pub fn minimize<C, FC, FB>(&mut self, start: &N, mut cost: FC, mut lower_bound: FB) -> Option<(N, C)>
+    where
+        C: Cost,
+        FC: FnMut(&N) -> C,
+        FB: FnMut(&N) -> C,
+    {
+        let mut best: Option<(N, C)> = None;
+        let mut stack = Vec::new();
+        self.push(start.clone(), &mut stack);
+
+        while let Some(state) = stack.pop() {
+            self.visitor.on_pop(&state, ());
+            if best.as_ref().is_some_and(|(_, best)| lower_bound(&state) >= *best) {
+                continue;
+            }
+            if self.is_pruned(&state) {
+                continue;
+            }
+            self.visitor.on_settle(&state, ());
+            if (self.is_goal)(&state) {
+                let state_cost = cost(&state);
+                if best.as_ref().is_none_or(|(_, best)| state_cost < *best) {
+                    best = Some((state, state_cost));
+                }
+                continue;
+            }
+            self.push_successors(&state, &mut stack);
+        }
+
+        self.visitor.on_finish();
+        best
+    }
+}
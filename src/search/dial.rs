@@ -0,0 +1,134 @@
+use super::{Exploration, SearchResult, Tracking, Visitor};
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+/// The largest edge cost the bucket queue accepts.
+pub const MAX_DIAL_EDGE_COST: u64 = 1 << 16;
+
+/// Dijkstra's algorithm with Dial's bucket queue instead of a binary heap, for `u64` edge costs.
+///
+/// The frontier is a window of buckets, one per distance, from the node being expanded up to the
+/// largest edge cost ahead of it. Pushing and popping are O(1), at the price of walking past
+/// empty buckets, so this pays off when edge costs are small integers or only take a few
+/// different values, like 1 and 1000. Nodes do not need to be `Ord`.
+///
+/// Takes the same arguments and returns the same as `dijkstra`, and likewise for the
+/// `_with_path` and `_with_visitor` variants, so a caller can switch queues by changing the
+/// function name. The window holds one bucket per possible distance ahead, so edge costs above
+/// `MAX_DIAL_EDGE_COST` panic; use `dijkstra` for those.
+pub fn dijkstra_dial<N, FN, IN, FS>(start: &N, successors: FN, is_goal: FS) -> Option<(N, u64)>
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, u64)>,
+    FS: FnMut(&N) -> bool,
+{
+    bucketed(start, successors, is_goal, Tracking::Nothing, &mut ()).goal
+}
+
+/// Like `dijkstra_with_path`, using a bucket queue.
+pub fn dijkstra_dial_with_path<N, FN, IN, FS>(start: &N, successors: FN, is_goal: FS) -> Option<SearchResult<N>>
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, u64)>,
+    FS: FnMut(&N) -> bool,
+{
+    bucketed(start, successors, is_goal, Tracking::Paths, &mut ()).into_result()
+}
+
+/// Like `dijkstra_with_visitor`, using a bucket queue.
+pub fn dijkstra_dial_with_visitor<N, FN, IN, FS, V>(
+    start: &N,
+    successors: FN,
+    is_goal: FS,
+    visitor: &mut V,
+) -> Option<SearchResult<N>>
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, u64)>,
+    FS: FnMut(&N) -> bool,
+    V: Visitor<N, u64>,
+{
+    bucketed(start, successors, is_goal, Tracking::Paths, visitor).into_result()
+}
+
+fn bucketed<N, FN, IN, FS, V>(
+    start: &N,
+    mut successors: FN,
+    mut is_goal: FS,
+    tracking: Tracking,
+    visitor: &mut V,
+) -> Exploration<N, u64>
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, u64)>,
+    FS: FnMut(&N) -> bool,
+    V: Visitor<N, u64>,
+{
+    let mut distances: HashMap<N, u64> = HashMap::new();
+    let mut settled: HashMap<N, u64> = HashMap::new();
+    let mut predecessors: HashMap<N, N> = HashMap::new();
+    // buckets[i] holds the nodes at distance base + i.
+    let mut buckets: VecDeque<Vec<N>> = VecDeque::from([vec![start.clone()]]);
+    let mut base = 0;
+
+    distances.insert(start.clone(), 0);
+    visitor.on_push(start, 0);
+
+    while let Some(bucket) = buckets.front_mut() {
+        let Some(current) = bucket.pop() else {
+            buckets.pop_front();
+            base += 1;
+            continue;
+        };
+        let current_dist = base;
+
+        visitor.on_pop(&current, current_dist);
+        if distances.get(&current).is_some_and(|&best| current_dist > best) {
+            continue;
+        }
+        visitor.on_settle(&current, current_dist);
+        if tracking != Tracking::Nothing {
+            settled.insert(current.clone(), current_dist);
+        }
+
+        if is_goal(&current) {
+            visitor.on_finish();
+            return Exploration {
+                goal: Some((current, current_dist)),
+                settled,
+                predecessors,
+            };
+        }
+
+        for (neighbor, cost) in successors(&current) {
+            assert!(cost <= MAX_DIAL_EDGE_COST, "dijkstra_dial only supports edge costs up to {}, got {}", MAX_DIAL_EDGE_COST, cost);
+
+            let new_dist = current_dist + cost;
+            if distances.get(&neighbor).is_none_or(|&best| new_dist < best) {
+                if tracking == Tracking::Paths {
+                    predecessors.insert(neighbor.clone(), current.clone());
+                }
+                distances.insert(neighbor.clone(), new_dist);
+                visitor.on_relax(&current, &neighbor, new_dist);
+                visitor.on_push(&neighbor, new_dist);
+
+                let index = cost as usize;
+                if buckets.len() <= index {
+                    buckets.resize_with(index + 1, Vec::new);
+                }
+                buckets[index].push(neighbor);
+            }
+        }
+    }
+
+    visitor.on_finish();
+    Exploration {
+        goal: None,
+        settled,
+        predecessors,
+    }
+}
@@ -0,0 +1,328 @@
+use super::{best_first, Cost, Tracking, Visitor};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
+use std::hash::Hash;
+
+/// A path waiting to be taken by Yen's algorithm: its cost, its nodes and the cost of reaching
+/// each of them, ordered so the cheapest comes out of a max-heap first.
+type Candidate<N, C> = Reverse<(C, Vec<N>, Vec<C>)>;
+
+/// The `k` cheapest loopless paths from `start` to a goal, cheapest first, found with Yen's
+/// algorithm. Each path is returned with its cost; ties are ordered by their nodes. When more
+/// paths tie with the `k`-th than fit, which of them are returned is deterministic, but they are
+/// not necessarily the first by their nodes. A path stops at the first goal it reaches. Returns fewer than `k` paths if there
+/// are no more.
+pub fn k_shortest_paths<N, C, FN, IN, FS>(start: &N, successors: FN, is_goal: FS, k: usize) -> Vec<(Vec<N>, C)>
+where
+    N: Eq + Hash + Clone + Ord,
+    C: Cost,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FS: FnMut(&N) -> bool,
+{
+    k_shortest_paths_with_visitor(start, successors, is_goal, k, &mut ())
+}
+
+/// Like `k_shortest_paths`, reporting every step of every spur search to `visitor`, which sees
+/// one `on_finish` per search.
+pub fn k_shortest_paths_with_visitor<N, C, FN, IN, FS, V>(
+    start: &N,
+    mut successors: FN,
+    mut is_goal: FS,
+    k: usize,
+    visitor: &mut V,
+) -> Vec<(Vec<N>, C)>
+where
+    N: Eq + Hash + Clone + Ord,
+    C: Cost,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FS: FnMut(&N) -> bool,
+    V: Visitor<N, C>,
+{
+    if k == 0 {
+        return Vec::new();
+    }
+
+    // Paths are kept with the cost of reaching each of their nodes, so a root can be reused
+    // without walking it again.
+    let mut found: Vec<(Vec<N>, Vec<C>)> = Vec::new();
+    let mut candidates: BinaryHeap<Candidate<N, C>> = BinaryHeap::new();
+    let mut seen: HashSet<Vec<N>> = HashSet::new();
+
+    let Some(first) = spur_search(start, &mut successors, &mut is_goal, &HashSet::new(), &HashSet::new(), visitor) else {
+        return Vec::new();
+    };
+    seen.insert(first.0.clone());
+    candidates.push(Reverse((*first.1.last().unwrap(), first.0, first.1)));
+
+    while let Some(Reverse((_, path, costs))) = candidates.pop() {
+        found.push((path, costs));
+        if found.len() == k {
+            break;
+        }
+
+        // Every later path leaves the last one found at some node, the spur, by an edge that no
+        // path found so far takes after the same root.
+        let (path, costs) = found.last().unwrap();
+        for i in 0..path.len() - 1 {
+            let root = &path[..=i];
+            let banned_nodes: HashSet<N> = root[..i].iter().cloned().collect();
+            let banned_edges: HashSet<N> = found
+                .iter()
+                .filter(|(other, _)| other.len() > i + 1 && other[..=i] == *root)
+                .map(|(other, _)| other[i + 1].clone())
+                .collect();
+
+            let Some((spur_path, spur_costs)) = spur_search(&path[i], &mut successors, &mut is_goal, &banned_nodes, &banned_edges, visitor) else {
+                continue;
+            };
+            let mut candidate = root[..i].to_vec();
+            candidate.extend(spur_path);
+            if seen.insert(candidate.clone()) {
+                let mut candidate_costs = costs[..i].to_vec();
+                candidate_costs.extend(spur_costs.into_iter().map(|c| costs[i] + c));
+                candidates.push(Reverse((*candidate_costs.last().unwrap(), candidate, candidate_costs)));
+            }
+        }
+    }
+
+    // Ties can be found out of order, since a later spur may find a path equal to one already
+    // taken.
+    let mut paths: Vec<(Vec<N>, C)> = found.into_iter().map(|(path, costs)| (path, *costs.last().unwrap())).collect();
+    paths.sort_unstable_by(|(a, a_cost), (b, b_cost)| a_cost.cmp(b_cost).then_with(|| a.cmp(b)));
+    paths
+}
+
+/// A shortest path from `spur` to a goal that avoids `banned_nodes` and does not leave `spur`
+/// towards any node in `banned_next`, with the cost of reaching each of its nodes.
+fn spur_search<N, C, FN, IN, FS, V>(
+    spur: &N,
+    successors: &mut FN,
+    is_goal: &mut FS,
+    banned_nodes: &HashSet<N>,
+    banned_next: &HashSet<N>,
+    visitor: &mut V,
+) -> Option<(Vec<N>, Vec<C>)>
+where
+    N: Eq + Hash + Clone + Ord,
+    C: Cost,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FS: FnMut(&N) -> bool,
+    V: Visitor<N, C>,
+{
+    let allowed = |node: &N| {
+        successors(node)
+            .into_iter()
+            .filter(|(next, _)| !banned_nodes.contains(next))
+            .filter(|(next, _)| node != spur || !banned_next.contains(next))
+            .collect::<Vec<_>>()
+    };
+    let result = best_first(vec![spur.clone()], allowed, |_| C::zero(), is_goal, Tracking::Paths, visitor).into_result()?;
+    let path = result.path();
+    let costs = path.iter().map(|node| result.distance(node).unwrap()).collect();
+    Some((path, costs))
+}
+
+/// Every loopless path from `start` to one of `goals` that costs at most `slack` more than the
+/// cheapest one, sorted by cost and then by nodes. A path stops at the first goal it reaches.
+///
+/// `reverse_successors` must return the edges leading *into* a node, as for `distance_to_goals`;
+/// for undirected graphs it is the same as `successors`. The distance from each node to the goals
+/// bounds the search, so only branches that can still finish within the budget are explored. The
+/// number of paths can still grow quickly with `slack`.
+pub fn near_optimal_paths<N, C, I, FN, IN, FR, IR>(
+    start: &N,
+    goals: I,
+    successors: FN,
+    reverse_successors: FR,
+    slack: C,
+) -> Vec<(Vec<N>, C)>
+where
+    N: Eq + Hash + Clone + Ord,
+    C: Cost,
+    I: IntoIterator<Item = N>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FR: FnMut(&N) -> IR,
+    IR: IntoIterator<Item = (N, C)>,
+{
+    near_optimal_paths_with_visitor(start, goals, successors, reverse_successors, slack, &mut ())
+}
+
+/// Like `near_optimal_paths`, reporting to `visitor` first every step of the search back from the
+/// goals and then the enumeration: a node is pushed and settled when a path is extended to it,
+/// and popped when the path backs off it again. Each phase ends with an `on_finish`.
+pub fn near_optimal_paths_with_visitor<N, C, I, FN, IN, FR, IR, V>(
+    start: &N,
+    goals: I,
+    mut successors: FN,
+    reverse_successors: FR,
+    slack: C,
+    visitor: &mut V,
+) -> Vec<(Vec<N>, C)>
+where
+    N: Eq + Hash + Clone + Ord,
+    C: Cost,
+    I: IntoIterator<Item = N>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FR: FnMut(&N) -> IR,
+    IR: IntoIterator<Item = (N, C)>,
+    V: Visitor<N, C>,
+{
+    let goals: HashSet<N> = goals.into_iter().collect();
+    let to_goal = best_first(goals.iter().cloned().collect(), reverse_successors, |_| C::zero(), |_| false, Tracking::Distances, &mut *visitor).settled;
+    let Some(&best) = to_goal.get(start) else {
+        return Vec::new();
+    };
+    if goals.contains(start) {
+        return vec![(vec![start.clone()], C::zero())];
+    }
+    let budget = best + slack;
+
+    let mut paths = Vec::new();
+    let mut path = vec![start.clone()];
+    let mut costs = vec![C::zero()];
+    let mut on_path: HashSet<N> = HashSet::from([start.clone()]);
+    visitor.on_push(start, C::zero());
+    visitor.on_settle(start, C::zero());
+    let mut stack = vec![successors(start).into_iter().collect::<Vec<_>>().into_iter()];
+
+    while let Some(edges) = stack.last_mut() {
+        let Some((next, cost)) = edges.next() else {
+            stack.pop();
+            let node = path.pop().unwrap();
+            visitor.on_pop(&node, costs.pop().unwrap());
+            on_path.remove(&node);
+            continue;
+        };
+        let Some(&rest) = to_goal.get(&next) else {
+            continue;
+        };
+        let so_far = *costs.last().unwrap() + cost;
+        if on_path.contains(&next) || so_far + rest > budget {
+            continue;
+        }
+
+        visitor.on_relax(path.last().unwrap(), &next, so_far);
+        if goals.contains(&next) {
+            let mut complete = path.clone();
+            complete.push(next);
+            paths.push((complete, so_far));
+        } else {
+            visitor.on_push(&next, so_far);
+            visitor.on_settle(&next, so_far);
+            stack.push(successors(&next).into_iter().collect::<Vec<_>>().into_iter());
+            on_path.insert(next.clone());
+            path.push(next);
+            costs.push(so_far);
+        }
+    }
+
+    visitor.on_finish();
+    paths.sort_unstable_by(|(a, a_cost), (b, b_cost)| a_cost.cmp(b_cost).then_with(|| a.cmp(b)));
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Point;
+    use crate::search::tests::{diamonds, open_grid_successors, weighted};
+    use crate::search::SearchStats;
+    use std::time::{Duration, Instant};
+
+    fn diamonds_reversed(node: &u32) -> Vec<(u32, u64)> {
+        (0..9)
+            .flat_map(|from| diamonds(&from).into_iter().map(move |(to, cost)| (from, to, cost)))
+            .filter(|(_, to, _)| to == node)
+            .map(|(from, _, cost)| (from, cost))
+            .collect()
+    }
+
+    #[test]
+    fn test_k_shortest_paths() {
+        let paths = k_shortest_paths(&0, diamonds, |n| *n == 8, 20);
+        assert_eq!(paths.len(), 10);
+        assert_eq!(paths.iter().map(|(_, c)| *c).collect::<Vec<_>>(), vec![6, 6, 6, 6, 6, 6, 6, 6, 8, 8]);
+        assert_eq!(paths[0].0, vec![0, 1, 3, 4, 6, 8]);
+        assert_eq!(paths[9].0, vec![0, 2, 3, 7, 8]);
+        assert_eq!(paths.iter().map(|(p, _)| p).collect::<HashSet<_>>().len(), 10);
+        assert_eq!(k_shortest_paths(&0, diamonds, |n| *n == 8, 3).len(), 3);
+
+        // The second-best route in `weighted` is the direct edge, then the shortcut from 2 to 3.
+        let paths = k_shortest_paths(&0, weighted, |n| *n == 3, 5);
+        assert_eq!(paths, vec![(vec![0, 2, 1, 3], 3), (vec![0, 2, 3], 8), (vec![0, 1, 3], 11)]);
+        assert_eq!(k_shortest_paths(&3, weighted, |n| *n == 0, 5), vec![]);
+        assert_eq!(k_shortest_paths(&0, weighted, |n| *n == 0, 5), vec![(vec![0], 0)]);
+    }
+
+    #[test]
+    fn test_near_optimal_paths() {
+        let exact = near_optimal_paths(&0, [8], diamonds, diamonds_reversed, 0);
+        assert_eq!(exact.len(), 8);
+        assert_eq!(exact, k_shortest_paths(&0, diamonds, |n| *n == 8, 8));
+        assert_eq!(near_optimal_paths(&0, [8], diamonds, diamonds_reversed, 1).len(), 8);
+        assert_eq!(near_optimal_paths(&0, [8], diamonds, diamonds_reversed, 2), k_shortest_paths(&0, diamonds, |n| *n == 8, 20));
+
+        // On an undirected grid both enumerations agree on every loopless path within the slack.
+        let (start, goal) = (Point::new(0, 0), Point::new(0, 4));
+        let successors = open_grid_successors(5);
+        let near = near_optimal_paths(&start, [goal], &successors, &successors, 4);
+        let yen = k_shortest_paths(&start, &successors, |p| *p == goal, near.len() + 1);
+        assert!(near.len() > 10);
+        assert!(near.iter().all(|(_, c)| *c <= 16));
+        assert_eq!(near[..], yen[..near.len()]);
+        assert!(yen[near.len()].1 > 16);
+
+        assert_eq!(near_optimal_paths(&start, [Point::new(10, 10)], &successors, &successors, 4), vec![]);
+    }
+
+    #[test]
+    fn test_k_shortest_paths_with_ties_at_k() {
+        // Eight routes cost 6, so only three of them fit; every call picks the same three.
+        let all = k_shortest_paths(&0, diamonds, |n| *n == 8, 20);
+        let paths = k_shortest_paths(&0, diamonds, |n| *n == 8, 3);
+        assert_eq!(paths, k_shortest_paths(&0, diamonds, |n| *n == 8, 3));
+        assert!(paths.iter().all(|path| path.1 == 6 && all.contains(path)));
+        assert!(paths.windows(2).all(|w| w[0].0 < w[1].0));
+    }
+
+    #[test]
+    fn test_k_shortest_paths_on_open_grid() {
+        // Corner to corner on a 12x12 grid, 705432 shortest routes tie for the first place. Yen's
+        // algorithm must stop after k of them rather than enumerate the ties.
+        let open = |p: &Point| {
+            [Point::new(p.row + 1, p.col), Point::new(p.row, p.col + 1), Point::new(p.row - 1, p.col), Point::new(p.row, p.col - 1)]
+                .into_iter()
+                .filter(|q| (0..12).contains(&q.row) && (0..12).contains(&q.col))
+                .map(|q| (q, 1u64))
+                .collect::<Vec<_>>()
+        };
+        let goal = Point::new(11, 11);
+        let started = Instant::now();
+        for k in [1, 5] {
+            let paths = k_shortest_paths(&Point::new(0, 0), open, |p| *p == goal, k);
+            assert_eq!(paths.len(), k);
+            assert!(paths.iter().all(|(path, cost)| *cost == 22 && path.len() == 23));
+        }
+        assert!(started.elapsed() < Duration::from_secs(5), "took {:?}", started.elapsed());
+    }
+
+    #[test]
+    fn test_kpaths_with_visitor() {
+        // Four nodes for the first path, then seven spur searches settling 12 more between them.
+        let mut stats = SearchStats::new("");
+        assert_eq!(k_shortest_paths_with_visitor(&0, weighted, |n| *n == 3, 5, &mut stats).len(), 3);
+        assert_eq!(stats.expanded, 16);
+
+        // The backward search settles all nine nodes, then the enumeration expands the last node
+        // of each of the 19 proper prefixes of the eight routes.
+        let mut stats = SearchStats::new("");
+        let paths = near_optimal_paths_with_visitor(&0, [8], diamonds, diamonds_reversed, 0, &mut stats);
+        assert_eq!(paths, near_optimal_paths(&0, [8], diamonds, diamonds_reversed, 0));
+        assert_eq!(stats.expanded, 9 + 19);
+    }
+}
@@ -0,0 +1,260 @@
+use super::{best_first, Cost, DistanceMap, Tracking, Visitor};
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::ops::Index;
+
+/// Shortest distances between every pair of a set of nodes, indexed either by node or by the
+/// node's position in `nodes()`. `matrix[(i, j)]` is the distance from node `i` to node `j`, or
+/// `None` if there is no path.
+#[derive(Debug, Clone)]
+pub struct DistanceMatrix<N, C = u64> {
+    nodes: Vec<N>,
+    indices: HashMap<N, usize>,
+    distances: Vec<Option<C>>,
+    routes: Routes<N, C>,
+}
+
+/// How a `DistanceMatrix` recovers its paths.
+#[derive(Debug, Clone)]
+enum Routes<N, C> {
+    /// The index of the next node on the path from `i` to `j`, for paths made only of matrix
+    /// nodes.
+    Next(Vec<Option<usize>>),
+    /// One single-source search per node, for paths through nodes outside the matrix.
+    Searches(Vec<DistanceMap<N, C>>),
+}
+
+impl<N, C> DistanceMatrix<N, C>
+where
+    N: Eq + Hash + Clone,
+    C: Cost,
+{
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn nodes(&self) -> &[N] {
+        &self.nodes
+    }
+
+    pub fn index_of(&self, node: &N) -> Option<usize> {
+        self.indices.get(node).copied()
+    }
+
+    /// The distance from `from` to `to`, or `None` if either is not in the matrix or there is no
+    /// path.
+    pub fn distance(&self, from: &N, to: &N) -> Option<C> {
+        self[(self.index_of(from)?, self.index_of(to)?)]
+    }
+
+    /// A shortest path from `from` to `to`, both included.
+    pub fn path(&self, from: &N, to: &N) -> Option<Vec<N>> {
+        let (i, j) = (self.index_of(from)?, self.index_of(to)?);
+        self[(i, j)]?;
+        match &self.routes {
+            Routes::Next(next) => {
+                let n = self.len();
+                let mut path = vec![self.nodes[i].clone()];
+                let mut current = i;
+                while current != j {
+                    current = next[current * n + j].unwrap();
+                    path.push(self.nodes[current].clone());
+                }
+                Some(path)
+            }
+            Routes::Searches(searches) => searches[i].path_to(to),
+        }
+    }
+}
+
+impl<N, C> Index<(usize, usize)> for DistanceMatrix<N, C> {
+    type Output = Option<C>;
+
+    fn index(&self, (from, to): (usize, usize)) -> &Self::Output {
+        &self.distances[from * self.nodes.len() + to]
+    }
+}
+
+fn index_nodes<N, I>(nodes: I) -> (Vec<N>, HashMap<N, usize>)
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut indices: HashMap<N, usize> = HashMap::new();
+    let mut unique = Vec::new();
+    for node in nodes {
+        if !indices.contains_key(&node) {
+            indices.insert(node.clone(), unique.len());
+            unique.push(node);
+        }
+    }
+    (unique, indices)
+}
+
+/// All-pairs shortest paths with Floyd–Warshall, in O(n³) for n nodes. Suited to small, dense
+/// graphs; edges to nodes outside `nodes` are ignored.
+pub fn floyd_warshall<N, C, I, FN, IN>(nodes: I, mut successors: FN) -> DistanceMatrix<N, C>
+where
+    N: Eq + Hash + Clone,
+    C: Cost,
+    I: IntoIterator<Item = N>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+{
+    let (nodes, indices) = index_nodes(nodes);
+    let n = nodes.len();
+    let mut distances: Vec<Option<C>> = vec![None; n * n];
+    let mut next: Vec<Option<usize>> = vec![None; n * n];
+
+    for i in 0..n {
+        distances[i * n + i] = Some(C::zero());
+        next[i * n + i] = Some(i);
+        for (to, cost) in successors(&nodes[i]) {
+            let Some(&j) = indices.get(&to) else {
+                continue;
+            };
+            if distances[i * n + j].is_none_or(|d| cost < d) {
+                distances[i * n + j] = Some(cost);
+                next[i * n + j] = Some(j);
+            }
+        }
+    }
+
+    for k in 0..n {
+        for i in 0..n {
+            let Some(ik) = distances[i * n + k] else {
+                continue;
+            };
+            for j in 0..n {
+                let Some(kj) = distances[k * n + j] else {
+                    continue;
+                };
+                let through_k = ik + kj;
+                if distances[i * n + j].is_none_or(|d| through_k < d) {
+                    distances[i * n + j] = Some(through_k);
+                    next[i * n + j] = next[i * n + k];
+                }
+            }
+        }
+    }
+
+    DistanceMatrix {
+        nodes,
+        indices,
+        distances,
+        routes: Routes::Next(next),
+    }
+}
+
+/// Shortest distances between every pair of `nodes`, running one Dijkstra search from each.
+/// Paths may pass through any other node of the graph, so this suits a few points of interest in
+/// a large implicit graph. Each search stops once it has reached every node in the set.
+pub fn distance_matrix<N, C, I, FN, IN>(nodes: I, successors: FN) -> DistanceMatrix<N, C>
+where
+    N: Eq + Hash + Clone + Ord,
+    C: Cost,
+    I: IntoIterator<Item = N>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+{
+    distance_matrix_with_visitor(nodes, successors, &mut ())
+}
+
+/// Like `distance_matrix`, reporting every step of every search to `visitor`, which sees one
+/// `on_finish` per node. `floyd_warshall` has no frontier to report on, so it has no such
+/// variant.
+pub fn distance_matrix_with_visitor<N, C, I, FN, IN, V>(nodes: I, mut successors: FN, visitor: &mut V) -> DistanceMatrix<N, C>
+where
+    N: Eq + Hash + Clone + Ord,
+    C: Cost,
+    I: IntoIterator<Item = N>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    V: Visitor<N, C>,
+{
+    let (nodes, indices) = index_nodes(nodes);
+    let n = nodes.len();
+    let mut distances: Vec<Option<C>> = vec![None; n * n];
+    let mut searches = Vec::with_capacity(n);
+
+    for (i, source) in nodes.iter().enumerate() {
+        let mut remaining: HashSet<&N> = nodes.iter().collect();
+        let is_goal = |node: &N| {
+            remaining.remove(node);
+            remaining.is_empty()
+        };
+        let exploration = best_first(vec![source.clone()], &mut successors, |_| C::zero(), is_goal, Tracking::Paths, &mut *visitor);
+        let search = DistanceMap {
+            distances: exploration.settled,
+            predecessors: exploration.predecessors,
+        };
+        for (j, target) in nodes.iter().enumerate() {
+            distances[i * n + j] = search.distance(target);
+        }
+        searches.push(search);
+    }
+
+    DistanceMatrix {
+        nodes,
+        indices,
+        distances,
+        routes: Routes::Searches(searches),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Point;
+    use crate::search::tests::{open_grid_successors, weighted};
+    use crate::search::{dijkstra, SearchStats};
+
+    #[test]
+    fn test_floyd_warshall() {
+        let matrix = floyd_warshall(0..4, weighted);
+        assert_eq!(matrix.len(), 4);
+        assert_eq!(matrix[(0, 3)], Some(3));
+        assert_eq!(matrix[(3, 0)], None);
+        assert_eq!(matrix[(2, 2)], Some(0));
+        assert_eq!(matrix.distance(&2, &3), Some(2));
+        assert_eq!(matrix.path(&0, &3), Some(vec![0, 2, 1, 3]));
+        assert_eq!(matrix.path(&1, &1), Some(vec![1]));
+        assert_eq!(matrix.path(&3, &0), None);
+
+        // Without node 2 the detour is not available.
+        let matrix = floyd_warshall([0, 1, 3], weighted);
+        assert_eq!(matrix.distance(&0, &3), Some(11));
+        assert_eq!(matrix.distance(&0, &2), None);
+    }
+
+    #[test]
+    fn test_distance_matrix() {
+        let points = [Point::new(0, 0), Point::new(0, 6), Point::new(6, 6)];
+        let matrix = distance_matrix(points, open_grid_successors(7));
+        let expected = floyd_warshall(points, |p| {
+            points.iter().map(|q| (*q, dijkstra(p, open_grid_successors(7), |r| r == q).unwrap().1)).collect::<Vec<_>>()
+        });
+        for i in 0..3 {
+            for j in 0..3 {
+                assert_eq!(matrix[(i, j)], expected[(i, j)]);
+            }
+        }
+        assert_eq!(matrix.distance(&points[0], &points[1]), Some(18));
+        let path = matrix.path(&points[1], &points[0]).unwrap();
+        assert_eq!(path.len(), 19);
+        assert_eq!(path.first(), Some(&points[1]));
+    }
+
+    #[test]
+    fn test_distance_matrix_with_visitor() {
+        // One search per node, each stopping once it has settled every node it can reach.
+        let mut stats = SearchStats::new("");
+        let matrix = distance_matrix_with_visitor(0..4, weighted, &mut stats);
+        assert_eq!(matrix.distance(&0, &3), Some(3));
+        assert_eq!(stats.expanded, 4 + 2 + 3 + 1);
+    }
+}
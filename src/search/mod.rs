@@ -0,0 +1,1223 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::hash::Hash;
+use crate::grid::Point;
+
+mod bfs;
+mod bidirectional;
+mod bisect;
+mod cost;
+mod dfs;
+mod dial;
+mod kpaths;
+mod matrix;
+mod reach;
+mod topo;
+mod visit;
+
+pub use bfs::{bfs, bfs_01, bfs_01_with_visitor, bfs_distance_map, bfs_with_visitor};
+pub use bidirectional::{bidirectional_bfs, bidirectional_bfs_with_visitor, bidirectional_dijkstra, bidirectional_dijkstra_with_visitor};
+pub use bisect::{first_true, first_true_unbounded, last_true};
+pub use cost::{Cost, FloatCost, Lex};
+pub use dfs::Dfs;
+pub use dial::{dijkstra_dial, dijkstra_dial_with_path, dijkstra_dial_with_visitor, MAX_DIAL_EDGE_COST};
+pub use kpaths::{k_shortest_paths, k_shortest_paths_with_visitor, near_optimal_paths, near_optimal_paths_with_visitor};
+pub use matrix::{distance_matrix, distance_matrix_with_visitor, floyd_warshall, DistanceMatrix};
+pub use reach::{count_paths, count_paths_from, count_paths_from_with_visitor, reachable, reachable_from, reachable_from_with_visitor};
+pub use topo::{toposort, toposort_with_visitor, PartialOrder};
+pub use visit::{take_recorded_stats, SearchStats, Visitor};
+
+/// The outcome of a search that kept track of how every node was reached.
+#[derive(Debug, Clone)]
+pub struct SearchResult<N, C = u64> {
+    pub goal: N,
+    pub cost: C,
+    settled: HashMap<N, C>,
+    predecessors: HashMap<N, N>,
+}
+
+impl<N, C> SearchResult<N, C>
+where
+    N: Eq + Hash + Clone,
+    C: Cost,
+{
+    /// The nodes from the start to the goal, both included.
+    pub fn path(&self) -> Vec<N> {
+        self.path_to(&self.goal).unwrap()
+    }
+
+    /// The cheapest route from the start to any node settled before the search stopped.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        if !self.settled.contains_key(node) {
+            return None;
+        }
+
+        let mut path = vec![node.clone()];
+        while let Some(previous) = self.predecessors.get(path.last().unwrap()) {
+            path.push(previous.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// The final distance to a node, if the search settled it before stopping.
+    pub fn distance(&self, node: &N) -> Option<C> {
+        self.settled.get(node).copied()
+    }
+
+    /// Every node whose distance is final, with that distance.
+    pub fn settled(&self) -> impl Iterator<Item = (&N, C)> + '_ {
+        self.settled.iter().map(|(n, d)| (n, *d))
+    }
+}
+
+pub fn dijkstra<N, C, FN, IN, FS>(start: &N, successors: FN, is_goal: FS) -> Option<(N, C)>
+where
+    N: Eq + Hash + Clone + Ord,
+    C: Cost,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FS: FnMut(&N) -> bool,
+{
+    astar(start, successors, |_| C::zero(), is_goal)
+}
+
+/// Like `dijkstra`, but keeps a predecessor map so the route can be recovered afterwards.
+pub fn dijkstra_with_path<N, C, FN, IN, FS>(start: &N, successors: FN, is_goal: FS) -> Option<SearchResult<N, C>>
+where
+    N: Eq + Hash + Clone + Ord,
+    C: Cost,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FS: FnMut(&N) -> bool,
+{
+    best_first(vec![start.clone()], successors, |_| C::zero(), is_goal, Tracking::Paths, &mut ()).into_result()
+}
+
+/// A* search. `heuristic` estimates the remaining cost from a node to the nearest goal and must
+/// never overestimate it, otherwise the returned cost may not be optimal. Debug builds check the
+/// heuristic at the start, at the goal and, when the route is kept, at every node along it, and
+/// panic where it overestimates.
+pub fn astar<N, C, FN, IN, FH, FS>(start: &N, successors: FN, heuristic: FH, is_goal: FS) -> Option<(N, C)>
+where
+    N: Eq + Hash + Clone + Ord,
+    C: Cost,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FH: FnMut(&N) -> C,
+    FS: FnMut(&N) -> bool,
+{
+    best_first(vec![start.clone()], successors, heuristic, is_goal, Tracking::Nothing, &mut ()).goal
+}
+
+/// Like `astar`, but keeps a predecessor map so the route can be recovered afterwards.
+pub fn astar_with_path<N, C, FN, IN, FH, FS>(
+    start: &N,
+    successors: FN,
+    heuristic: FH,
+    is_goal: FS,
+) -> Option<SearchResult<N, C>>
+where
+    N: Eq + Hash + Clone + Ord,
+    C: Cost,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FH: FnMut(&N) -> C,
+    FS: FnMut(&N) -> bool,
+{
+    best_first(vec![start.clone()], successors, heuristic, is_goal, Tracking::Paths, &mut ()).into_result()
+}
+
+/// Like `dijkstra_with_path`, reporting every step of the search to `visitor`.
+pub fn dijkstra_with_visitor<N, C, FN, IN, FS, V>(
+    start: &N,
+    successors: FN,
+    is_goal: FS,
+    visitor: &mut V,
+) -> Option<SearchResult<N, C>>
+where
+    N: Eq + Hash + Clone + Ord,
+    C: Cost,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FS: FnMut(&N) -> bool,
+    V: Visitor<N, C>,
+{
+    best_first(vec![start.clone()], successors, |_| C::zero(), is_goal, Tracking::Paths, visitor).into_result()
+}
+
+/// Like `astar_with_path`, reporting every step of the search to `visitor`.
+pub fn astar_with_visitor<N, C, FN, IN, FH, FS, V>(
+    start: &N,
+    successors: FN,
+    heuristic: FH,
+    is_goal: FS,
+    visitor: &mut V,
+) -> Option<SearchResult<N, C>>
+where
+    N: Eq + Hash + Clone + Ord,
+    C: Cost,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FH: FnMut(&N) -> C,
+    FS: FnMut(&N) -> bool,
+    V: Visitor<N, C>,
+{
+    best_first(vec![start.clone()], successors, heuristic, is_goal, Tracking::Paths, visitor).into_result()
+}
+
+/// How much of its bookkeeping a search hands back to the caller.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+enum Tracking {
+    Nothing,
+    Distances,
+    Paths,
+}
+
+/// Everything a best-first search learned before it stopped.
+struct Exploration<N, C> {
+    goal: Option<(N, C)>,
+    settled: HashMap<N, C>,
+    predecessors: HashMap<N, N>,
+}
+
+impl<N, C> Exploration<N, C> {
+    fn into_result(self) -> Option<SearchResult<N, C>> {
+        self.goal.map(|(goal, cost)| SearchResult {
+            goal,
+            cost,
+            settled: self.settled,
+            predecessors: self.predecessors,
+        })
+    }
+}
+
+fn best_first<N, C, FN, IN, FH, FS, V>(
+    starts: Vec<N>,
+    mut successors: FN,
+    mut heuristic: FH,
+    mut is_goal: FS,
+    tracking: Tracking,
+    visitor: &mut V,
+) -> Exploration<N, C>
+where
+    N: Eq + Hash + Clone + Ord,
+    C: Cost,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FH: FnMut(&N) -> C,
+    FS: FnMut(&N) -> bool,
+    V: Visitor<N, C>,
+{
+    let mut distances: HashMap<N, C> = HashMap::new();
+    let mut settled: HashMap<N, C> = HashMap::new();
+    let mut predecessors: HashMap<N, N> = HashMap::new();
+    // Entries are ordered by estimated total cost, breaking ties in favour of the node that is
+    // furthest along, which is usually closer to the goal.
+    let mut queue: BinaryHeap<(Reverse<C>, C, N)> = BinaryHeap::new();
+
+    for start in starts.iter() {
+        distances.insert(start.clone(), C::zero());
+        visitor.on_push(start, C::zero());
+        queue.push((Reverse(heuristic(start)), C::zero(), start.clone()));
+    }
+
+    while let Some((_, current_dist, current)) = queue.pop() {
+        visitor.on_pop(&current, current_dist);
+        if distances.get(&current).is_some_and(|&best| current_dist > best) {
+            continue;
+        }
+        visitor.on_settle(&current, current_dist);
+        if tracking != Tracking::Nothing {
+            settled.insert(current.clone(), current_dist);
+        }
+
+        if is_goal(&current) {
+            debug_assert!(
+                heuristic(&current) == C::zero(),
+                "heuristic overestimates: it is {:?} at a goal",
+                heuristic(&current),
+            );
+            debug_assert!(
+                starts.iter().map(&mut heuristic).min().unwrap_or(C::zero()) <= current_dist,
+                "heuristic overestimates: the goal is only {:?} away from the start",
+                current_dist,
+            );
+            // The rest of the route found is an upper bound on the true remaining cost.
+            #[cfg(debug_assertions)]
+            if tracking == Tracking::Paths {
+                let mut node = &current;
+                while let Some(previous) = predecessors.get(node) {
+                    node = previous;
+                    let estimate = heuristic(node);
+                    debug_assert!(
+                        distances[node] + estimate <= current_dist,
+                        "heuristic overestimates: it is {:?} at a node {:?} into a route of cost {:?}",
+                        estimate,
+                        distances[node],
+                        current_dist,
+                    );
+                }
+            }
+            visitor.on_finish();
+            return Exploration {
+                goal: Some((current, current_dist)),
+                settled,
+                predecessors,
+            };
+        }
+
+        for (neighbor, cost) in successors(&current) {
+            debug_assert!(cost >= C::zero(), "negative edge cost {:?}", cost);
+
+            let new_dist = current_dist + cost;
+            if distances.get(&neighbor).is_none_or(|&best| new_dist < best) {
+                let estimate = heuristic(&neighbor);
+                if tracking == Tracking::Paths {
+                    predecessors.insert(neighbor.clone(), current.clone());
+                }
+                distances.insert(neighbor.clone(), new_dist);
+                visitor.on_relax(&current, &neighbor, new_dist);
+                visitor.on_push(&neighbor, new_dist);
+                queue.push((Reverse(new_dist + estimate), new_dist, neighbor));
+            }
+        }
+    }
+
+    visitor.on_finish();
+    Exploration {
+        goal: None,
+        settled,
+        predecessors,
+    }
+}
+
+/// Heuristic for 4-way movement on a grid with unit step costs.
+pub fn manhattan_heuristic(goal: Point) -> impl Fn(&Point) -> u64 {
+    move |point| point.manhattan_distance(&goal) as u64
+}
+
+/// Heuristic for 8-way movement on a grid with unit step costs.
+pub fn chebyshev_heuristic(goal: Point) -> impl Fn(&Point) -> u64 {
+    move |point| point.chebyshev_distance(&goal) as u64
+}
+
+/// Every shortest path from a start node to the cheapest goals, stored as a DAG of predecessors
+/// rather than as materialised paths.
+///
+/// The graph must not contain cycles of zero-cost edges, otherwise the DAG would not be acyclic.
+#[derive(Debug, Clone)]
+pub struct ShortestPathDag<N, C = u64> {
+    pub start: N,
+    pub cost: C,
+    goals: Vec<N>,
+    predecessors: HashMap<N, Vec<N>>,
+}
+
+impl<N, C> ShortestPathDag<N, C>
+where
+    N: Eq + Hash + Clone,
+    C: Cost,
+{
+    /// All goals that are reachable at the optimal cost.
+    pub fn goals(&self) -> &[N] {
+        &self.goals
+    }
+
+    /// The nodes that directly precede `node` on some shortest path.
+    pub fn predecessors(&self, node: &N) -> &[N] {
+        self.predecessors.get(node).map_or(&[], |ps| ps.as_slice())
+    }
+
+    /// Every node that lies on at least one optimal path, including the start and the goals.
+    pub fn nodes(&self) -> HashSet<N> {
+        let mut seen: HashSet<N> = self.goals.iter().cloned().collect();
+        let mut stack = self.goals.clone();
+        while let Some(node) = stack.pop() {
+            for previous in self.predecessors(&node) {
+                if seen.insert(previous.clone()) {
+                    stack.push(previous.clone());
+                }
+            }
+        }
+        seen
+    }
+
+    /// The number of distinct optimal paths, summed over all goals.
+    pub fn count_paths(&self) -> u128 {
+        let mut counts: HashMap<&N, u128> = HashMap::new();
+        counts.insert(&self.start, 1);
+
+        // Post-order walk backwards from the goals, so every predecessor is counted first.
+        let mut stack: Vec<(&N, bool)> = self.goals.iter().map(|g| (g, false)).collect();
+        while let Some((node, expanded)) = stack.pop() {
+            if counts.contains_key(node) {
+                continue;
+            }
+            let predecessors = self.predecessors(node);
+            if expanded {
+                let count = predecessors.iter().map(|p| counts[p]).sum();
+                counts.insert(node, count);
+            } else {
+                stack.push((node, true));
+                stack.extend(predecessors.iter().filter(|p| !counts.contains_key(p)).map(|p| (p, false)));
+            }
+        }
+
+        self.goals.iter().map(|g| counts[g]).sum()
+    }
+
+    /// Lazily enumerates every optimal path from the start to a goal. There can be exponentially
+    /// many, so prefer `count_paths` or `nodes` when the paths themselves are not needed.
+    pub fn paths(&self) -> ShortestPaths<'_, N, C> {
+        ShortestPaths {
+            dag: self,
+            goals: self.goals.iter(),
+            stack: Vec::new(),
+        }
+    }
+}
+
+/// Iterator over the paths of a `ShortestPathDag`, created by `ShortestPathDag::paths`.
+pub struct ShortestPaths<'a, N, C = u64> {
+    dag: &'a ShortestPathDag<N, C>,
+    goals: std::slice::Iter<'a, N>,
+    // The partial path walked backwards from a goal, with the index of the next predecessor to
+    // try at each node.
+    stack: Vec<(&'a N, usize)>,
+}
+
+impl<N, C> Iterator for ShortestPaths<'_, N, C>
+where
+    N: Eq + Hash + Clone,
+    C: Cost,
+{
+    type Item = Vec<N>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let Some((node, index)) = self.stack.last_mut() else {
+                self.stack.push((self.goals.next()?, 0));
+                continue;
+            };
+
+            if *node == &self.dag.start {
+                let path = self.stack.iter().rev().map(|(n, _)| (*n).clone()).collect();
+                self.stack.pop();
+                return Some(path);
+            }
+
+            match self.dag.predecessors(node).get(*index) {
+                Some(previous) => {
+                    *index += 1;
+                    self.stack.push((previous, 0));
+                }
+                None => {
+                    self.stack.pop();
+                }
+            }
+        }
+    }
+}
+
+/// Finds every shortest path to the cheapest goals. Unlike `dijkstra`, the search keeps going
+/// until no other goal can be reached at the same cost, and records all tied predecessors.
+pub fn dijkstra_all_shortest_paths<N, C, FN, IN, FS>(
+    start: &N,
+    successors: FN,
+    is_goal: FS,
+) -> Option<ShortestPathDag<N, C>>
+where
+    N: Eq + Hash + Clone + Ord,
+    C: Cost,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FS: FnMut(&N) -> bool,
+{
+    dijkstra_all_shortest_paths_with_visitor(start, successors, is_goal, &mut ())
+}
+
+/// Like `dijkstra_all_shortest_paths`, reporting every step of the search to `visitor`. Tied
+/// predecessors are reported as relaxations as well.
+pub fn dijkstra_all_shortest_paths_with_visitor<N, C, FN, IN, FS, V>(
+    start: &N,
+    mut successors: FN,
+    mut is_goal: FS,
+    visitor: &mut V,
+) -> Option<ShortestPathDag<N, C>>
+where
+    N: Eq + Hash + Clone + Ord,
+    C: Cost,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FS: FnMut(&N) -> bool,
+    V: Visitor<N, C>,
+{
+    let mut distances: HashMap<N, C> = HashMap::new();
+    let mut predecessors: HashMap<N, Vec<N>> = HashMap::new();
+    let mut queue: BinaryHeap<(Reverse<C>, N)> = BinaryHeap::new();
+    let mut goals: Vec<N> = Vec::new();
+    let mut best: Option<C> = None;
+
+    distances.insert(start.clone(), C::zero());
+    visitor.on_push(start, C::zero());
+    queue.push((Reverse(C::zero()), start.clone()));
+
+    while let Some((Reverse(current_dist), current)) = queue.pop() {
+        visitor.on_pop(&current, current_dist);
+        if best.is_some_and(|best| current_dist > best) {
+            break;
+        }
+        if distances.get(&current).is_some_and(|&best| current_dist > best) {
+            continue;
+        }
+        visitor.on_settle(&current, current_dist);
+
+        if is_goal(&current) {
+            best = Some(current_dist);
+            goals.push(current.clone());
+        }
+
+        for (neighbor, cost) in successors(&current) {
+            if neighbor == *start {
+                continue;
+            }
+
+            let new_dist = current_dist + cost;
+            let known_dist = distances.get(&neighbor).copied();
+
+            if known_dist.is_none_or(|known| new_dist < known) {
+                // Found a shorter path, forget the previous predecessors
+                distances.insert(neighbor.clone(), new_dist);
+                predecessors.insert(neighbor.clone(), vec![current.clone()]);
+                visitor.on_relax(&current, &neighbor, new_dist);
+                visitor.on_push(&neighbor, new_dist);
+                queue.push((Reverse(new_dist), neighbor));
+            } else if known_dist == Some(new_dist) {
+                // Found an equally short path, remember it as well
+                predecessors.get_mut(&neighbor).unwrap().push(current.clone());
+                visitor.on_relax(&current, &neighbor, new_dist);
+            }
+        }
+    }
+
+    visitor.on_finish();
+
+    best.map(|cost| ShortestPathDag {
+        start: start.clone(),
+        cost,
+        goals,
+        predecessors,
+    })
+}
+
+/// Shortest distances from a set of sources to every node they can reach.
+#[derive(Debug, Clone)]
+pub struct DistanceMap<N, C = u64> {
+    distances: HashMap<N, C>,
+    predecessors: HashMap<N, N>,
+}
+
+impl<N, C> DistanceMap<N, C>
+where
+    N: Eq + Hash + Clone,
+    C: Cost,
+{
+    /// The distance from the nearest source, or `None` if no source reaches the node.
+    pub fn distance(&self, node: &N) -> Option<C> {
+        self.distances.get(node).copied()
+    }
+
+    /// The cheapest route from the nearest source to `node`, both included.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        if !self.distances.contains_key(node) {
+            return None;
+        }
+
+        let mut path = vec![node.clone()];
+        while let Some(previous) = self.predecessors.get(path.last().unwrap()) {
+            path.push(previous.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    pub fn distances(&self) -> &HashMap<N, C> {
+        &self.distances
+    }
+
+    pub fn into_distances(self) -> HashMap<N, C> {
+        self.distances
+    }
+}
+
+/// Runs Dijkstra from every source at once and returns the distance from the nearest source to
+/// every reachable node.
+pub fn distance_map<N, C, I, FN, IN>(sources: I, successors: FN) -> HashMap<N, C>
+where
+    N: Eq + Hash + Clone + Ord,
+    C: Cost,
+    I: IntoIterator<Item = N>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+{
+    let sources = sources.into_iter().collect();
+    best_first(sources, successors, |_| C::zero(), |_| false, Tracking::Distances, &mut ()).settled
+}
+
+/// Like `distance_map`, but also keeps a predecessor map so each route can be recovered.
+pub fn distance_map_with_paths<N, C, I, FN, IN>(sources: I, successors: FN) -> DistanceMap<N, C>
+where
+    N: Eq + Hash + Clone + Ord,
+    C: Cost,
+    I: IntoIterator<Item = N>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+{
+    distance_map_with_visitor(sources, successors, &mut ())
+}
+
+/// Like `distance_map_with_paths`, reporting every step of the search to `visitor`.
+pub fn distance_map_with_visitor<N, C, I, FN, IN, V>(sources: I, successors: FN, visitor: &mut V) -> DistanceMap<N, C>
+where
+    N: Eq + Hash + Clone + Ord,
+    C: Cost,
+    I: IntoIterator<Item = N>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    V: Visitor<N, C>,
+{
+    let sources = sources.into_iter().collect();
+    let exploration = best_first(sources, successors, |_| C::zero(), |_| false, Tracking::Paths, visitor);
+    DistanceMap {
+        distances: exploration.settled,
+        predecessors: exploration.predecessors,
+    }
+}
+
+/// The distance from every node to the nearest goal. `reverse_successors` must return the edges
+/// leading *into* a node, as `(predecessor, cost)` pairs. For undirected graphs this is the same
+/// as the successor closure.
+pub fn distance_to_goals<N, C, I, FN, IN>(goals: I, reverse_successors: FN) -> HashMap<N, C>
+where
+    N: Eq + Hash + Clone + Ord,
+    C: Cost,
+    I: IntoIterator<Item = N>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+{
+    distance_map(goals, reverse_successors)
+}
+
+/// Like `distance_to_goals`, reporting every step of the search to `visitor`.
+pub fn distance_to_goals_with_visitor<N, C, I, FN, IN, V>(goals: I, reverse_successors: FN, visitor: &mut V) -> HashMap<N, C>
+where
+    N: Eq + Hash + Clone + Ord,
+    C: Cost,
+    I: IntoIterator<Item = N>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    V: Visitor<N, C>,
+{
+    let goals = goals.into_iter().collect();
+    best_first(goals, reverse_successors, |_| C::zero(), |_| false, Tracking::Distances, visitor).settled
+}
+
+/// Runs Dijkstra until it has settled the `k` nearest nodes matching `is_goal`, and returns them
+/// with their distances, nearest first. Returns fewer if fewer goals are reachable.
+pub fn dijkstra_nearest_goals<N, C, FN, IN, FS>(start: &N, successors: FN, is_goal: FS, k: usize) -> Vec<(N, C)>
+where
+    N: Eq + Hash + Clone + Ord,
+    C: Cost,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FS: FnMut(&N) -> bool,
+{
+    dijkstra_nearest_goals_with_visitor(start, successors, is_goal, k, &mut ())
+}
+
+/// Like `dijkstra_nearest_goals`, reporting every step of the search to `visitor`.
+pub fn dijkstra_nearest_goals_with_visitor<N, C, FN, IN, FS, V>(
+    start: &N,
+    successors: FN,
+    mut is_goal: FS,
+    k: usize,
+    visitor: &mut V,
+) -> Vec<(N, C)>
+where
+    N: Eq + Hash + Clone + Ord,
+    C: Cost,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FS: FnMut(&N) -> bool,
+    V: Visitor<N, C>,
+{
+    if k == 0 {
+        return Vec::new();
+    }
+
+    let mut found = Vec::new();
+    let collect = |node: &N| {
+        if is_goal(node) {
+            found.push(node.clone());
+        }
+        found.len() == k
+    };
+    let exploration = best_first(vec![start.clone()], successors, |_| C::zero(), collect, Tracking::Distances, visitor);
+    found.into_iter().map(|goal| (goal.clone(), exploration.settled[&goal])).collect()
+}
+
+/// Runs Dijkstra until every node in `goals` is settled, and returns the reachable ones with
+/// their distances, nearest first.
+pub fn dijkstra_all_goals<N, C, I, FN, IN>(start: &N, successors: FN, goals: I) -> Vec<(N, C)>
+where
+    N: Eq + Hash + Clone + Ord,
+    C: Cost,
+    I: IntoIterator<Item = N>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+{
+    dijkstra_all_goals_with_visitor(start, successors, goals, &mut ())
+}
+
+/// Like `dijkstra_all_goals`, reporting every step of the search to `visitor`.
+pub fn dijkstra_all_goals_with_visitor<N, C, I, FN, IN, V>(start: &N, successors: FN, goals: I, visitor: &mut V) -> Vec<(N, C)>
+where
+    N: Eq + Hash + Clone + Ord,
+    C: Cost,
+    I: IntoIterator<Item = N>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    V: Visitor<N, C>,
+{
+    let goals: HashSet<N> = goals.into_iter().collect();
+    dijkstra_nearest_goals_with_visitor(start, successors, |node| goals.contains(node), goals.len(), visitor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Direction::{E, N, S, W};
+
+    pub(super) fn open_grid_successors(size: i32) -> impl Fn(&Point) -> Vec<(Point, u64)> {
+        move |point| {
+            [N, E, S, W]
+                .iter()
+                .map(|d| point.move_direction(d))
+                .filter(|p| p.row >= 0 && p.col >= 0 && p.row < size && p.col < size)
+                .filter(|p| !(p.col == 3 && p.row < size - 1))
+                .map(|p| (p, 1))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_astar_matches_dijkstra() {
+        let goal = Point::new(0, 6);
+        let successors = open_grid_successors(7);
+        let expected = dijkstra(&Point::new(0, 0), &successors, |p| *p == goal);
+        let result = astar(&Point::new(0, 0), &successors, manhattan_heuristic(goal), |p| *p == goal);
+        assert_eq!(result, Some((goal, 18)));
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_astar_unreachable() {
+        let goal = Point::new(10, 10);
+        let result = astar(&Point::new(0, 0), open_grid_successors(7), chebyshev_heuristic(goal), |p| *p == goal);
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_dijkstra_with_path() {
+        let goal = Point::new(0, 6);
+        let result = dijkstra_with_path(&Point::new(0, 0), open_grid_successors(7), |p| *p == goal).unwrap();
+        let path = result.path();
+        assert_eq!(result.cost, 18);
+        assert_eq!(path.len(), 19);
+        assert_eq!(path.first(), Some(&Point::new(0, 0)));
+        assert_eq!(path.last(), Some(&goal));
+        assert!(path.windows(2).all(|w| w[0].manhattan_distance(&w[1]) == 1));
+
+        assert_eq!(result.distance(&Point::new(6, 3)), Some(9));
+        assert_eq!(result.path_to(&Point::new(6, 3)).map(|p| p.len()), Some(10));
+        assert_eq!(result.distance(&Point::new(0, 3)), None);
+    }
+
+    #[test]
+    fn test_astar_with_path_matches_cost() {
+        let goal = Point::new(0, 6);
+        let result = astar_with_path(&Point::new(0, 0), open_grid_successors(7), manhattan_heuristic(goal), |p| *p == goal).unwrap();
+        assert_eq!(result.path().len() as u64, result.cost + 1);
+    }
+
+    // Two routes across each of the three diamonds, so eight shortest paths in total.
+    pub(super) fn diamonds(node: &u32) -> Vec<(u32, u64)> {
+        match node {
+            0 => vec![(1, 1), (2, 1)],
+            1 | 2 => vec![(3, 1)],
+            3 => vec![(4, 2), (5, 1), (7, 5)],
+            5 => vec![(4, 1)],
+            4 => vec![(6, 1), (7, 1)],
+            6 => vec![(8, 1)],
+            7 => vec![(8, 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn test_shortest_path_dag() {
+        let dag = dijkstra_all_shortest_paths(&0, diamonds, |n| *n == 8).unwrap();
+        assert_eq!(dag.cost, 6);
+        assert_eq!(dag.goals(), &[8]);
+        assert_eq!(dag.count_paths(), 8);
+        assert_eq!(dag.nodes(), (0..=8).collect::<HashSet<u32>>());
+
+        let paths: Vec<Vec<u32>> = dag.paths().collect();
+        assert_eq!(paths.len(), 8);
+        assert!(paths.iter().all(|p| p.first() == Some(&0) && p.last() == Some(&8)));
+        assert_eq!(paths.iter().collect::<HashSet<_>>().len(), 8);
+    }
+
+    #[test]
+    fn test_shortest_path_dag_multiple_goals() {
+        let dag = dijkstra_all_shortest_paths(&0, diamonds, |n| *n == 6 || *n == 7).unwrap();
+        assert_eq!(dag.cost, 5);
+        assert_eq!(dag.goals().len(), 2);
+        assert_eq!(dag.count_paths(), 8);
+        assert!(!dag.nodes().contains(&8));
+    }
+
+    // A directed graph where the direct edge 0 -> 1 is much more expensive than going via 2.
+    pub(super) fn weighted(node: &u32) -> Vec<(u32, u64)> {
+        match node {
+            0 => vec![(1, 10), (2, 1)],
+            2 => vec![(1, 1), (3, 7)],
+            1 => vec![(3, 1)],
+            _ => vec![],
+        }
+    }
+
+    pub(super) fn weighted_reversed(node: &u32) -> Vec<(u32, u64)> {
+        (0..4)
+            .flat_map(|from| weighted(&from).into_iter().map(move |(to, cost)| (from, to, cost)))
+            .filter(|(_, to, _)| to == node)
+            .map(|(from, _, cost)| (from, cost))
+            .collect()
+    }
+
+    #[test]
+    fn test_distance_map_weighted() {
+        let distances = distance_map([0], weighted);
+        assert_eq!(distances, HashMap::from([(0, 0), (1, 2), (2, 1), (3, 3)]));
+    }
+
+    #[test]
+    fn test_distance_map_multiple_sources() {
+        let distances = distance_map_with_paths([0, 1], weighted);
+        assert_eq!(distances.distance(&1), Some(0));
+        assert_eq!(distances.distance(&3), Some(1));
+        assert_eq!(distances.path_to(&3), Some(vec![1, 3]));
+        assert_eq!(distances.path_to(&2), Some(vec![0, 2]));
+        assert_eq!(distances.path_to(&4), None);
+    }
+
+    #[test]
+    fn test_distance_to_goals_directed() {
+        let distances = distance_to_goals([3], weighted_reversed);
+        assert_eq!(distances, HashMap::from([(0, 3), (1, 1), (2, 2), (3, 0)]));
+
+        let mut stats = SearchStats::new("distance to goals");
+        assert_eq!(distance_to_goals_with_visitor([3], weighted_reversed, &mut stats), distances);
+        assert_eq!(stats.expanded, 4);
+    }
+
+    #[test]
+    fn test_lexicographic_tuple_costs() {
+        // Both routes from 0 to 3 take 4 steps, but the one through 2 needs fewer turns.
+        let successors = |node: &u32| -> Vec<(u32, Lex<(u32, u32)>)> {
+            match node {
+                0 => vec![(1, Lex((2, 1))), (2, Lex((2, 0)))],
+                1 => vec![(3, Lex((2, 0)))],
+                2 => vec![(3, Lex((2, 1)))],
+                _ => vec![],
+            }
+        };
+        let result = dijkstra_with_path(&0, successors, |n| *n == 3).unwrap();
+        assert_eq!(result.cost, Lex((4, 1)));
+        assert_eq!(result.path(), vec![0, 2, 3]);
+        assert_eq!(dijkstra(&3, successors, |n| *n == 0), None);
+
+        assert_eq!(Lex((1u8, 2u8, 3u8)) + Lex((1, 0, 2)), Lex((2, 2, 5)));
+        assert!(Lex((0u64, 1i32, 0u8)) < Lex((1, 0, 0)));
+        assert!(<Lex<(u64, u64)> as num_traits::Zero>::zero() == Lex((0, 0)));
+    }
+
+    #[test]
+    fn test_float_and_signed_costs() {
+        let diagonal = |p: &Point| -> Vec<(Point, FloatCost)> {
+            vec![
+                (Point::new(p.row + 1, p.col), FloatCost(1.0)),
+                (Point::new(p.row, p.col + 1), FloatCost(1.0)),
+                (Point::new(p.row + 1, p.col + 1), FloatCost(std::f64::consts::SQRT_2)),
+            ]
+        };
+        let (_, cost) = dijkstra(&Point::new(0, 0), diagonal, |p| *p == Point::new(2, 3)).unwrap();
+        assert!((cost.0 - (1.0 + 2.0 * std::f64::consts::SQRT_2)).abs() < 1e-9);
+
+        let distances = distance_map([0i32], |n: &i32| {
+            if *n < 5 { vec![(n + 1, 2i64)] } else { vec![] }
+        });
+        assert_eq!(distances.get(&5), Some(&10i64));
+    }
+
+    #[derive(Default)]
+    struct EventLog {
+        pushes: Vec<u32>,
+        settled: Vec<u32>,
+        relaxed: Vec<(u32, u32, u64)>,
+        finished: bool,
+    }
+
+    impl Visitor<u32, u64> for EventLog {
+        fn on_push(&mut self, node: &u32, _cost: u64) {
+            self.pushes.push(*node);
+        }
+
+        fn on_settle(&mut self, node: &u32, _cost: u64) {
+            self.settled.push(*node);
+        }
+
+        fn on_relax(&mut self, from: &u32, to: &u32, cost: u64) {
+            self.relaxed.push((*from, *to, cost));
+        }
+
+        fn on_finish(&mut self) {
+            self.finished = true;
+        }
+    }
+
+    #[test]
+    fn test_visitor_sees_every_event() {
+        let mut log = EventLog::default();
+        let result = dijkstra_with_visitor(&0, weighted, |n| *n == 3, &mut log).unwrap();
+        assert_eq!(result.cost, 3);
+        assert_eq!(log.settled, vec![0, 2, 1, 3]);
+        assert_eq!(log.relaxed, vec![(0, 1, 10), (0, 2, 1), (2, 1, 2), (2, 3, 8), (1, 3, 3)]);
+        assert_eq!(log.pushes, vec![0, 1, 2, 1, 3, 3]);
+        assert!(log.finished);
+    }
+
+    #[test]
+    fn test_search_stats() {
+        let mut stats = SearchStats::new("weighted");
+        distance_map_with_visitor([0], weighted, &mut stats);
+        assert_eq!(stats.expanded, 4);
+        assert_eq!(stats.duplicate_pushes, 2);
+        assert_eq!(stats.max_frontier, 3);
+        assert!(stats.to_string().starts_with("weighted: 4 expanded, max frontier 3, 2 duplicate pushes"));
+
+        let mut stats = SearchStats::new("");
+        dijkstra_all_shortest_paths_with_visitor(&0, diamonds, |n| *n == 8, &mut stats).unwrap();
+        assert_eq!(stats.expanded, 9);
+
+        // An optional visitor forwards everything when present.
+        let mut optional = Some(SearchStats::new(""));
+        distance_map_with_visitor([0], weighted, &mut optional);
+        assert_eq!(optional.map(|stats| stats.expanded), Some(4));
+        assert!(SearchStats::requested("").is_none());
+
+        stats.record();
+        assert_eq!(take_recorded_stats().len(), 1);
+        assert!(take_recorded_stats().is_empty());
+    }
+
+    fn unit_successors(size: i32) -> impl Fn(&Point) -> Vec<Point> {
+        let weighted = open_grid_successors(size);
+        move |point| weighted(point).into_iter().map(|(p, _)| p).collect()
+    }
+
+    #[test]
+    fn test_bfs_matches_dijkstra() {
+        let goal = Point::new(0, 6);
+        let result = bfs(&Point::new(0, 0), unit_successors(7), |p| *p == goal).unwrap();
+        assert_eq!(result.cost, 18);
+        assert_eq!(result.path().len(), 19);
+        assert!(result.path().windows(2).all(|w| w[0].manhattan_distance(&w[1]) == 1));
+        assert!(bfs(&Point::new(0, 0), unit_successors(7), |p| *p == Point::new(9, 9)).is_none());
+
+        let expected = distance_map([Point::new(0, 0), goal], open_grid_successors(7));
+        let distances = bfs_distance_map([Point::new(0, 0), goal], unit_successors(7));
+        assert_eq!(distances.distances(), &expected);
+        assert_eq!(distances.path_to(&Point::new(6, 3)).map(|p| p.len()), Some(10));
+    }
+
+    #[test]
+    fn test_bfs_01() {
+        // Walking right is free, walking down costs 1, so the cheapest route hugs the top row.
+        let successors = |p: &Point| -> Vec<(Point, u64)> {
+            let mut result = vec![];
+            if p.col < 4 {
+                result.push((Point::new(p.row, p.col + 1), 0));
+            }
+            if p.row < 4 {
+                result.push((Point::new(p.row + 1, p.col), 1));
+            }
+            result
+        };
+        let goal = Point::new(4, 4);
+        let result = bfs_01(&Point::new(0, 0), successors, |p| *p == goal).unwrap();
+        assert_eq!(result.cost, 4);
+        assert_eq!(dijkstra(&Point::new(0, 0), successors, |p| *p == goal), Some((goal, 4)));
+        assert_eq!(result.path().len(), 9);
+        assert_eq!(result.distance(&Point::new(0, 4)), Some(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "edge costs of 0 and 1")]
+    fn test_bfs_01_rejects_heavy_edges() {
+        bfs_01(&0, weighted, |n| *n == 3);
+    }
+
+    #[test]
+    fn test_dial_matches_dijkstra() {
+        assert_eq!(dijkstra_dial(&0, weighted, |n| *n == 3), Some((3, 3)));
+        assert_eq!(dijkstra_dial(&3, weighted, |n| *n == 0), None);
+
+        let result = dijkstra_dial_with_path(&0, diamonds, |n| *n == 8).unwrap();
+        assert_eq!(result.cost, 6);
+        assert_eq!(result.path().first(), Some(&0));
+
+        // Costs of 1 and 1000 like the reindeer maze, with a free edge thrown in.
+        let successors = |n: &u32| -> Vec<(u32, u64)> {
+            match n {
+                0 => vec![(1, 1000), (2, 1)],
+                2 => vec![(3, 0), (1, 1000)],
+                3 => vec![(1, 998)],
+                _ => vec![],
+            }
+        };
+        let result = dijkstra_dial_with_path(&0, successors, |n| *n == 1).unwrap();
+        assert_eq!(result.cost, 999);
+        assert_eq!(result.path(), vec![0, 2, 3, 1]);
+        assert_eq!(dijkstra(&0, successors, |n| *n == 1), Some((1, 999)));
+        let mut stats = SearchStats::new("");
+        let result = dijkstra_dial_with_visitor(&0, successors, |n| *n == 1, &mut stats).unwrap();
+        assert_eq!((result.cost, result.path()), (999, vec![0, 2, 3, 1]));
+        assert_eq!(stats.expanded, 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "dijkstra_dial only supports edge costs up to 65536, got 65537")]
+    fn test_dijkstra_dial_rejects_huge_edge_costs() {
+        dijkstra_dial(&0u32, |n| vec![(n + 1, MAX_DIAL_EDGE_COST + 1)], |n| *n == 1);
+    }
+
+    #[test]
+    fn test_dfs_find_any() {
+        // Reach exactly 10 from 1 by doubling or adding 3.
+        let successors = |n: &u64| vec![n * 2, n + 3];
+        assert_eq!(Dfs::new(successors, |n| *n == 10).prune(|n| *n > 10).find_any(&1), Some(10));
+        assert_eq!(Dfs::new(successors, |n| *n == 3).prune(|n| *n > 3).memoize().find_any(&1), None);
+
+        // Depth first, doubling before adding: 1, 2, 4, 8, then 7 and finally 10.
+        let mut stats = SearchStats::new("");
+        assert_eq!(Dfs::new(successors, |n| *n == 10).visitor(&mut stats).prune(|n| *n > 10).find_any(&1), Some(10));
+        assert_eq!(stats.expanded, 6);
+    }
+
+    #[test]
+    fn test_dfs_count_all() {
+        assert_eq!(Dfs::new(|n: &u32| diamonds(n).into_iter().map(|(m, _)| m), |n| *n == 8).count_all(&0), 10);
+
+        // Compositions of 40 into parts of 1 and 2, which only finishes in time with memoisation.
+        let successors = |n: &u32| vec![n + 1, n + 2];
+        let count = Dfs::new(successors, |n| *n == 40).prune(|n| *n > 40).memoize().count_all(&0);
+        assert_eq!(count, 165580141);
+        let count = Dfs::new(successors, |n| *n == 10).prune(|n| *n > 10).count_all(&0);
+        assert_eq!(count, 89);
+
+        // With memoisation every value up to 40 is expanded exactly once.
+        let mut stats = SearchStats::new("");
+        let count = Dfs::new(successors, |n| *n == 40).prune(|n| *n > 40).memoize().visitor(&mut stats).count_all(&0);
+        assert_eq!(count, 165580141);
+        assert_eq!(stats.expanded, 41);
+    }
+
+    #[test]
+    fn test_dfs_minimize() {
+        // The fewest operations to reach 10 from 1, as (value, steps) states.
+        let successors = |(n, steps): &(u64, u64)| vec![(n * 2, steps + 1), (n + 3, steps + 1)];
+        let best = Dfs::new(successors, |(n, _)| *n == 10)
+            .prune(|(n, _)| *n > 10)
+            .minimize(&(1, 0), |(_, steps)| *steps, |(_, steps)| *steps);
+        assert_eq!(best, Some(((10, 3), 3)));
+    }
+
+    fn diamond_successors(node: &u32) -> Vec<u32> {
+        diamonds(node).into_iter().map(|(n, _)| n).collect()
+    }
+
+    #[test]
+    fn test_reachable() {
+        assert_eq!(reachable(&4, diamond_successors), HashSet::from([4, 6, 7, 8]));
+        assert_eq!(reachable_from([5, 6], diamond_successors), HashSet::from([4, 5, 6, 7, 8]));
+        assert_eq!(reachable(&8, diamond_successors), HashSet::from([8]));
+
+        let mut stats = SearchStats::new("");
+        assert_eq!(reachable_from_with_visitor([4, 6], diamond_successors, &mut stats).len(), 4);
+        assert_eq!((stats.expanded, stats.duplicate_pushes), (4, 0));
+    }
+
+    #[test]
+    fn test_count_paths() {
+        assert_eq!(count_paths(&0, diamond_successors, |n| *n == 8), 10);
+        assert_eq!(count_paths(&0, diamond_successors, |n| *n == 4), 4);
+        assert_eq!(count_paths_from([0, 3, 9], diamond_successors, |n| *n == 8), 15);
+
+        // Steps of 1 or 2 from 0 to 100 give Fibonacci(101) paths, which overflows a u64.
+        let ladder = |n: &u32| if *n < 300 { vec![n + 1, n + 2] } else { vec![] };
+        assert_eq!(count_paths(&0, ladder, |n| *n == 100), 573147844013817084101);
+
+        // Every node is expanded once however many paths run through it.
+        let mut stats = SearchStats::new("");
+        assert_eq!(count_paths_from_with_visitor([0], diamond_successors, |n| *n == 8, &mut stats), 10);
+        assert_eq!(stats.expanded, 9);
+    }
+
+    #[test]
+    #[should_panic(expected = "acyclic")]
+    fn test_count_paths_rejects_cycles() {
+        count_paths(&0, |n: &u32| vec![(n + 1) % 3], |n| *n == 5);
+    }
+
+    #[test]
+    fn test_toposort() {
+        assert_eq!(toposort(0..9, diamond_successors), Ok(vec![0, 1, 2, 3, 5, 4, 6, 7, 8]));
+        // Only the nodes asked for are ordered.
+        assert_eq!(toposort([8, 4, 7], diamond_successors), Ok(vec![4, 7, 8]));
+
+        let cyclic = |n: &u32| match n {
+            0 => vec![1],
+            1 => vec![2],
+            2 => vec![3],
+            3 => vec![1, 4],
+            _ => vec![],
+        };
+        assert_eq!(toposort(0..5, cyclic), Err(vec![1, 2, 3]));
+
+        // Nodes on or after the cycle are never placed.
+        let mut stats = SearchStats::new("");
+        assert!(toposort_with_visitor(0..5, cyclic, &mut stats).is_err());
+        assert_eq!(stats.expanded, 1);
+        let mut stats = SearchStats::new("");
+        assert!(toposort_with_visitor(0..9, diamond_successors, &mut stats).is_ok());
+        assert_eq!(stats.expanded, 9);
+    }
+
+    #[test]
+    fn test_partial_order() {
+        let order: PartialOrder<u32> = "47|53\n97|13\n97|61\n97|47\n75|29\n61|13\n75|53\n29|13\n97|29\n53|29\n61|53\n97|53\n61|29\n47|13\n75|47\n97|75\n47|61\n75|61\n47|29\n75|13\n53|13"
+            .parse()
+            .unwrap();
+        assert!(order.precedes(&47, &53));
+        assert!(order.is_consistent(&[75, 47, 61, 53, 29]));
+        assert_eq!(order.violations(&[75, 97, 47, 61, 53]), vec![(97, 75)]);
+        assert_eq!(
+            order.violations(&[13, 29, 53, 47, 13]),
+            vec![(29, 13), (53, 13), (53, 29), (47, 13), (47, 29), (47, 53)]
+        );
+        assert_eq!(order.linearize(&[75, 97, 47, 61, 53]), Ok(vec![97, 75, 47, 61, 53]));
+        assert_eq!(order.linearize(&[61, 13, 29]), Ok(vec![61, 29, 13]));
+
+        assert!("47-53".parse::<PartialOrder<u32>>().is_err());
+        assert!("47|x".parse::<PartialOrder<u32>>().is_err());
+    }
+
+    #[test]
+    fn test_first_and_last_true() {
+        assert_eq!(first_true(0..100, |x| x * x >= 50), Some(8));
+        assert_eq!(first_true(0..100, |_| true), Some(0));
+        assert_eq!(first_true(0..100, |_| false), None);
+        assert_eq!(first_true(5..5, |_| true), None);
+        assert_eq!(first_true(-50i64..50, |x| x >= -3), Some(-3));
+        assert_eq!(first_true(u64::MAX - 10..u64::MAX, |x| x >= u64::MAX - 2), Some(u64::MAX - 2));
+        // Signed ranges wider than the type's maximum.
+        assert_eq!(first_true(i32::MIN..i32::MAX, |x| x >= 0), Some(0));
+        assert_eq!(first_true(i32::MIN..i32::MAX, |_| true), Some(i32::MIN));
+        assert_eq!(first_true(i32::MIN..i32::MAX, |x| x >= i32::MAX - 1), Some(i32::MAX - 1));
+        assert_eq!(first_true(i32::MIN..i32::MAX, |_| false), None);
+        assert_eq!(first_true(i64::MIN..i64::MAX, |x| x > -7), Some(-6));
+        assert_eq!(last_true(i8::MIN..i8::MAX, |x| x < -100), Some(-101));
+
+        assert_eq!(last_true(0..100, |x| x * x <= 50), Some(7));
+        assert_eq!(last_true(0..100, |_| true), Some(99));
+        assert_eq!(last_true(0..100, |_| false), None);
+
+        let mut calls = 0;
+        first_true(0..1_000_000usize, |x| {
+            calls += 1;
+            x >= 123_456
+        });
+        assert!(calls <= 20);
+    }
+
+    #[test]
+    fn test_first_true_unbounded() {
+        assert_eq!(first_true_unbounded(0, |x| x * x >= 1_000_000_000_000), Some(1_000_000));
+        assert_eq!(first_true_unbounded(7, |_| true), Some(7));
+        assert_eq!(first_true_unbounded(0, |x| x >= 12), Some(12));
+        assert_eq!(first_true_unbounded(u64::MAX - 5, |_| false), None);
+    }
+
+    #[test]
+    fn test_multiple_goals() {
+        let start = Point::new(0, 0);
+        let corners = [Point::new(6, 6), Point::new(0, 6), Point::new(6, 0), Point::new(10, 10)];
+        let goals = dijkstra_all_goals(&start, open_grid_successors(7), corners);
+        assert_eq!(goals, vec![(Point::new(6, 0), 6), (Point::new(6, 6), 12), (Point::new(0, 6), 18)]);
+
+        let nearest = dijkstra_nearest_goals(&start, open_grid_successors(7), |p| corners.contains(p), 2);
+        assert_eq!(nearest, goals[..2]);
+        assert!(dijkstra_nearest_goals(&start, open_grid_successors(7), |p| corners.contains(p), 0).is_empty());
+
+        let even = dijkstra_nearest_goals(&0, diamonds, |n| n % 2 == 0, 10);
+        assert_eq!(even.iter().map(|&(_, c)| c).collect::<Vec<_>>(), vec![0, 1, 4, 5, 6]);
+
+        // The search stops at the last goal instead of settling the whole graph.
+        let mut stats = SearchStats::new("");
+        assert_eq!(dijkstra_all_goals_with_visitor(&0, diamonds, [4], &mut stats), vec![(4, 4)]);
+        assert_eq!(stats.expanded, 6);
+        let mut stats = SearchStats::new("");
+        assert_eq!(dijkstra_nearest_goals_with_visitor(&0, diamonds, |n| *n > 3, 1, &mut stats), vec![(5, 3)]);
+        assert_eq!(stats.expanded, 5);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "heuristic overestimates")]
+    fn test_astar_reports_overestimating_heuristic() {
+        let goal = Point::new(0, 6);
+        let heuristic = |p: &Point| 100 * p.manhattan_distance(&goal) as u64 + 1;
+        astar(&Point::new(0, 0), open_grid_successors(7), heuristic, |p| *p == goal);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "heuristic overestimates: it is 50 at a node 9 into a route of cost 18")]
+    fn test_astar_reports_overestimate_along_the_route() {
+        // Fine at the start and the goal, but far too high at the bottom of the wall.
+        let goal = Point::new(0, 6);
+        let heuristic = |p: &Point| if *p == Point::new(6, 3) { 50 } else { 0 };
+        astar_with_path(&Point::new(0, 0), open_grid_successors(7), heuristic, |p| *p == goal);
+    }
+
+    #[test]
+    fn test_astar_accepts_inconsistent_admissible_heuristic() {
+        // Never above the true distance, but it drops by more than an edge costs.
+        let goal = Point::new(0, 6);
+        let heuristic = |p: &Point| if p.col > 3 { p.manhattan_distance(&goal) as u64 } else { 0 };
+        let result = astar_with_path(&Point::new(0, 0), open_grid_successors(7), heuristic, |p| *p == goal).unwrap();
+        assert_eq!(result.cost, 18);
+    }
+}
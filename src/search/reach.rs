@@ -0,0 +1,144 @@
+use super::Visitor;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+/// Every node reachable from `start`, including `start` itself.
+pub fn reachable<N, FN, IN>(start: &N, successors: FN) -> HashSet<N>
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+{
+    reachable_from([start.clone()], successors)
+}
+
+/// Every node reachable from any of the sources, including the sources.
+pub fn reachable_from<N, I, FN, IN>(sources: I, successors: FN) -> HashSet<N>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+{
+    reachable_from_with_visitor(sources, successors, &mut ())
+}
+
+/// Like `reachable_from`, reporting every step of the search to `visitor`. Reachability has no
+/// costs, so the visitor sees `()` for each of them.
+pub fn reachable_from_with_visitor<N, I, FN, IN, V>(sources: I, mut successors: FN, visitor: &mut V) -> HashSet<N>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+    V: Visitor<N, ()>,
+{
+    let mut seen: HashSet<N> = HashSet::new();
+    let mut stack: Vec<N> = Vec::new();
+    for source in sources {
+        if seen.insert(source.clone()) {
+            visitor.on_push(&source, ());
+            stack.push(source);
+        }
+    }
+    while let Some(node) = stack.pop() {
+        visitor.on_pop(&node, ());
+        visitor.on_settle(&node, ());
+        for next in successors(&node) {
+            if seen.insert(next.clone()) {
+                visitor.on_relax(&node, &next, ());
+                visitor.on_push(&next, ());
+                stack.push(next);
+            }
+        }
+    }
+    visitor.on_finish();
+    seen
+}
+
+/// The number of distinct paths from `start` to a goal. A path stops at the first goal it
+/// reaches. Each node's count is computed once, so this is linear in the size of the graph, but
+/// the graph must be acyclic; a cycle panics.
+pub fn count_paths<N, FN, IN, FG>(start: &N, successors: FN, is_goal: FG) -> u128
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+    FG: FnMut(&N) -> bool,
+{
+    count_paths_from([start.clone()], successors, is_goal)
+}
+
+/// Like `count_paths`, summed over several sources. Counts are shared between the sources, so
+/// this is cheaper than calling `count_paths` for each of them.
+pub fn count_paths_from<N, I, FN, IN, FG>(sources: I, successors: FN, is_goal: FG) -> u128
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+    FG: FnMut(&N) -> bool,
+{
+    count_paths_from_with_visitor(sources, successors, is_goal, &mut ())
+}
+
+/// Like `count_paths_from`, reporting every step of the walk to `visitor`. Each node is settled
+/// once, when it is first expanded; the visitor sees `()` for every cost.
+pub fn count_paths_from_with_visitor<N, I, FN, IN, FG, V>(
+    sources: I,
+    mut successors: FN,
+    mut is_goal: FG,
+    visitor: &mut V,
+) -> u128
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+    FG: FnMut(&N) -> bool,
+    V: Visitor<N, ()>,
+{
+    let mut counts: HashMap<N, u128> = HashMap::new();
+    let mut in_progress: HashSet<N> = HashSet::new();
+    let mut total = 0;
+
+    for source in sources {
+        // Post-order walk: a node is counted once all of its successors have been.
+        visitor.on_push(&source, ());
+        let mut stack: Vec<(N, Option<Vec<N>>)> = vec![(source.clone(), None)];
+        while let Some((node, successors_of_node)) = stack.pop() {
+            if let Some(next) = successors_of_node {
+                let count = next.iter().map(|n| counts[n]).sum();
+                in_progress.remove(&node);
+                counts.insert(node, count);
+                continue;
+            }
+            visitor.on_pop(&node, ());
+            if counts.contains_key(&node) {
+                continue;
+            }
+            visitor.on_settle(&node, ());
+            if is_goal(&node) {
+                counts.insert(node, 1);
+                continue;
+            }
+
+            let next: Vec<N> = successors(&node).into_iter().collect();
+            let pending: Vec<N> = next.iter().filter(|n| !counts.contains_key(n)).cloned().collect();
+            assert!(
+                pending.iter().all(|n| !in_progress.contains(n) && *n != node),
+                "count_paths needs an acyclic graph"
+            );
+            in_progress.insert(node.clone());
+            stack.push((node, Some(next)));
+            for next in &pending {
+                visitor.on_push(next, ());
+            }
+            stack.extend(pending.into_iter().map(|n| (n, None)));
+        }
+        total += counts[&source];
+    }
+
+    visitor.on_finish();
+    total
+}
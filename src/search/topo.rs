@@ -0,0 +1,198 @@
+use super::Visitor;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::str::FromStr;
+
+/// Orders `nodes` so that every node comes before its successors, using Kahn's algorithm. Edges
+/// to nodes outside `nodes` are ignored. Ties are broken by the order of `nodes`, so the result
+/// is deterministic.
+///
+/// If the nodes cannot be ordered, returns one of the cycles that prevents it, with each node
+/// followed by its successor on the cycle.
+pub fn toposort<N, I, FN, IN>(nodes: I, successors: FN) -> Result<Vec<N>, Vec<N>>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+{
+    toposort_with_visitor(nodes, successors, &mut ())
+}
+
+/// Like `toposort`, reporting every step to `visitor`. A node is pushed once it has no
+/// predecessors left and settled when it is placed; each edge it removes is reported as a
+/// relaxation. The visitor sees `()` for every cost.
+pub fn toposort_with_visitor<N, I, FN, IN, V>(nodes: I, mut successors: FN, visitor: &mut V) -> Result<Vec<N>, Vec<N>>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+    V: Visitor<N, ()>,
+{
+    let mut nodes: Vec<N> = nodes.into_iter().collect();
+    let mut seen: HashSet<N> = HashSet::new();
+    nodes.retain(|n| seen.insert(n.clone()));
+
+    let mut edges: HashMap<N, Vec<N>> = HashMap::new();
+    let mut in_degree: HashMap<N, usize> = nodes.iter().map(|n| (n.clone(), 0)).collect();
+    for node in nodes.iter() {
+        let next: Vec<N> = successors(node).into_iter().filter(|n| seen.contains(n)).collect();
+        for n in next.iter() {
+            *in_degree.get_mut(n).unwrap() += 1;
+        }
+        edges.insert(node.clone(), next);
+    }
+
+    let mut ready: VecDeque<N> = nodes.iter().filter(|n| in_degree[*n] == 0).cloned().collect();
+    for node in ready.iter() {
+        visitor.on_push(node, ());
+    }
+    let mut sorted = Vec::with_capacity(nodes.len());
+    while let Some(node) = ready.pop_front() {
+        visitor.on_pop(&node, ());
+        visitor.on_settle(&node, ());
+        for next in edges[&node].iter() {
+            visitor.on_relax(&node, next, ());
+            let degree = in_degree.get_mut(next).unwrap();
+            *degree -= 1;
+            if *degree == 0 {
+                visitor.on_push(next, ());
+                ready.push_back(next.clone());
+            }
+        }
+        sorted.push(node);
+    }
+
+    visitor.on_finish();
+    if sorted.len() == nodes.len() {
+        return Ok(sorted);
+    }
+
+    // Every node left over still has a predecessor that is left over, so walking backwards along
+    // those predecessors must eventually run into a cycle.
+    let left_over = |n: &N| in_degree[n] > 0;
+    let mut predecessor: HashMap<&N, &N> = HashMap::new();
+    for from in nodes.iter().filter(|n| left_over(n)) {
+        for to in edges[from].iter().filter(|n| left_over(n)) {
+            predecessor.entry(to).or_insert(from);
+        }
+    }
+
+    let mut walk: Vec<&N> = vec![nodes.iter().find(|n| left_over(n)).unwrap()];
+    loop {
+        let previous = predecessor[walk.last().unwrap()];
+        if let Some(index) = walk.iter().position(|n| *n == previous) {
+            let mut cycle: Vec<N> = walk[index..].iter().rev().map(|n| (*n).clone()).collect();
+            // Start the cycle at the node that was listed first.
+            let first = (0..cycle.len()).min_by_key(|&i| nodes.iter().position(|n| *n == cycle[i])).unwrap();
+            cycle.rotate_left(first);
+            return Err(cycle);
+        }
+        walk.push(previous);
+    }
+}
+
+/// A set of ordering rules between pairs of nodes, such as page `47` having to come before page
+/// `53`. The rules do not need to form a total order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartialOrder<N: Eq + Hash> {
+    after: HashMap<N, HashSet<N>>,
+}
+
+impl<N> Default for PartialOrder<N>
+where
+    N: Eq + Hash,
+{
+    fn default() -> Self {
+        PartialOrder { after: HashMap::new() }
+    }
+}
+
+impl<N> PartialOrder<N>
+where
+    N: Eq + Hash + Clone,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the rule that `before` has to come before `after`.
+    pub fn add(&mut self, before: N, after: N) {
+        self.after.entry(before).or_default().insert(after);
+    }
+
+    /// Whether there is a rule that `a` has to come before `b`.
+    pub fn precedes(&self, a: &N, b: &N) -> bool {
+        self.after.get(a).is_some_and(|after| after.contains(b))
+    }
+
+    /// Every rule broken by `sequence`, as `(before, after)` pairs where `after` appears first.
+    /// Only the rules themselves are checked, not the ones they imply. A node listed twice counts
+    /// at its first position. Takes time linear in the length of `sequence` plus the number of
+    /// rules that start at its nodes.
+    pub fn violations(&self, sequence: &[N]) -> Vec<(N, N)> {
+        let mut positions: HashMap<&N, usize> = HashMap::new();
+        for (i, node) in sequence.iter().enumerate() {
+            positions.entry(node).or_insert(i);
+        }
+
+        let mut violations = Vec::new();
+        for (i, node) in sequence.iter().enumerate() {
+            if positions[node] != i {
+                continue;
+            }
+            if let Some(after) = self.after.get(node) {
+                let mut earlier: Vec<usize> = after.iter().filter_map(|n| positions.get(n).copied()).filter(|&j| j < i).collect();
+                earlier.sort_unstable();
+                violations.extend(earlier.into_iter().map(|j| (node.clone(), sequence[j].clone())));
+            }
+        }
+        violations
+    }
+
+    pub fn is_consistent(&self, sequence: &[N]) -> bool {
+        self.violations(sequence).is_empty()
+    }
+
+    /// Orders `subset` so that it satisfies every rule between its nodes, ignoring rules that
+    /// mention other nodes. Returns a cycle if the rules within the subset contradict each other.
+    pub fn linearize(&self, subset: &[N]) -> Result<Vec<N>, Vec<N>> {
+        toposort(subset.iter().cloned(), |n| self.after.get(n).into_iter().flatten().cloned())
+    }
+}
+
+impl<N> FromIterator<(N, N)> for PartialOrder<N>
+where
+    N: Eq + Hash + Clone,
+{
+    fn from_iter<I: IntoIterator<Item = (N, N)>>(rules: I) -> Self {
+        let mut order = PartialOrder::new();
+        for (before, after) in rules {
+            order.add(before, after);
+        }
+        order
+    }
+}
+
+impl<N> FromStr for PartialOrder<N>
+where
+    N: Eq + Hash + Clone + FromStr,
+{
+    type Err = String;
+
+    /// Parses one `before|after` rule per line.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                let (before, after) = line
+                    .trim()
+                    .split_once('|')
+                    .ok_or_else(|| format!("expected a rule like 47|53, got {:?}", line))?;
+                let parse = |s: &str| s.parse::<N>().map_err(|_| format!("invalid node {:?} in rule {:?}", s, line));
+                Ok((parse(before)?, parse(after)?))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,176 @@
+use std::cell::RefCell;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::time::{Duration, Instant};
+
+/// Callbacks fired by the search algorithms as they run. Every method does nothing by default,
+/// so a visitor only implements the events it cares about. `()` is the visitor that ignores
+/// everything.
+pub trait Visitor<N, C> {
+    /// A node was added to the frontier with the given cost so far.
+    fn on_push(&mut self, _node: &N, _cost: C) {}
+
+    /// A node was taken off the frontier. It may turn out to be a stale duplicate.
+    fn on_pop(&mut self, _node: &N, _cost: C) {}
+
+    /// A node's distance became final and its successors are about to be expanded.
+    fn on_settle(&mut self, _node: &N, _cost: C) {}
+
+    /// An edge improved the best known cost of `to`.
+    fn on_relax(&mut self, _from: &N, _to: &N, _cost: C) {}
+
+    /// The search returned.
+    fn on_finish(&mut self) {}
+}
+
+impl<N, C> Visitor<N, C> for () {}
+
+/// `None` ignores everything, so an optional visitor costs a branch per event when it is off.
+impl<N, C, V: Visitor<N, C>> Visitor<N, C> for Option<V> {
+    fn on_push(&mut self, node: &N, cost: C) {
+        if let Some(visitor) = self {
+            visitor.on_push(node, cost)
+        }
+    }
+
+    fn on_pop(&mut self, node: &N, cost: C) {
+        if let Some(visitor) = self {
+            visitor.on_pop(node, cost)
+        }
+    }
+
+    fn on_settle(&mut self, node: &N, cost: C) {
+        if let Some(visitor) = self {
+            visitor.on_settle(node, cost)
+        }
+    }
+
+    fn on_relax(&mut self, from: &N, to: &N, cost: C) {
+        if let Some(visitor) = self {
+            visitor.on_relax(from, to, cost)
+        }
+    }
+
+    fn on_finish(&mut self) {
+        if let Some(visitor) = self {
+            visitor.on_finish()
+        }
+    }
+}
+
+impl<N, C, V: Visitor<N, C>> Visitor<N, C> for &mut V {
+    fn on_push(&mut self, node: &N, cost: C) {
+        (**self).on_push(node, cost)
+    }
+
+    fn on_pop(&mut self, node: &N, cost: C) {
+        (**self).on_pop(node, cost)
+    }
+
+    fn on_settle(&mut self, node: &N, cost: C) {
+        (**self).on_settle(node, cost)
+    }
+
+    fn on_relax(&mut self, from: &N, to: &N, cost: C) {
+        (**self).on_relax(from, to, cost)
+    }
+
+    fn on_finish(&mut self) {
+        (**self).on_finish()
+    }
+}
+
+/// A visitor that measures how much work a search did.
+#[derive(Debug, Clone, Default)]
+pub struct SearchStats {
+    pub label: String,
+    /// Nodes whose successors were expanded.
+    pub expanded: usize,
+    /// The largest number of entries on the frontier at once, stale duplicates included.
+    pub max_frontier: usize,
+    /// Pushes of a node that had already been pushed before.
+    pub duplicate_pushes: usize,
+    pub elapsed: Duration,
+    frontier: usize,
+    pushed: HashSet<u64>,
+    started: Option<Instant>,
+}
+
+impl SearchStats {
+    pub fn new(label: &str) -> Self {
+        SearchStats {
+            label: label.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// New stats if the solution was started with `--stats` (`cargo solve <day> --stats`), and
+    /// `None` otherwise, so a day can leave its stats in place without paying for them.
+    pub fn requested(label: &str) -> Option<Self> {
+        std::env::args().any(|x| x == "--stats").then(|| SearchStats::new(label))
+    }
+
+    /// Hands the stats to the runner, which prints them after the part's timing. Only stats
+    /// recorded during the first run of a part are shown.
+    pub fn record(self) {
+        RECORDED.with(|recorded| recorded.borrow_mut().push(self));
+    }
+
+    fn start_clock(&mut self) {
+        if self.started.is_none() {
+            self.started = Some(Instant::now());
+        }
+    }
+}
+
+impl<N: Hash, C> Visitor<N, C> for SearchStats {
+    fn on_push(&mut self, node: &N, _cost: C) {
+        self.start_clock();
+        let mut hasher = DefaultHasher::new();
+        node.hash(&mut hasher);
+        if !self.pushed.insert(hasher.finish()) {
+            self.duplicate_pushes += 1;
+        }
+        self.frontier += 1;
+        self.max_frontier = self.max_frontier.max(self.frontier);
+    }
+
+    fn on_pop(&mut self, _node: &N, _cost: C) {
+        self.frontier = self.frontier.saturating_sub(1);
+    }
+
+    fn on_settle(&mut self, _node: &N, _cost: C) {
+        self.expanded += 1;
+    }
+
+    fn on_finish(&mut self) {
+        if let Some(started) = self.started {
+            self.elapsed += started.elapsed();
+            self.started = None;
+        }
+    }
+}
+
+impl Display for SearchStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if !self.label.is_empty() {
+            write!(f, "{}: ", self.label)?;
+        }
+        write!(
+            f,
+            "{} expanded, max frontier {}, {} duplicate pushes ({:.1?})",
+            self.expanded, self.max_frontier, self.duplicate_pushes, self.elapsed
+        )
+    }
+}
+
+thread_local! {
+    static RECORDED: RefCell<Vec<SearchStats>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Removes and returns every `SearchStats` recorded on this thread so far.
+pub fn take_recorded_stats() -> Vec<SearchStats> {
+    RECORDED.with(|recorded| recorded.take())
+}
@@ -0,0 +1,252 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Disjoint sets over the elements `0..len`, with path compression and union by size.
+#[derive(Debug, Clone)]
+pub struct DenseUnionFind {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    components: usize,
+}
+
+impl DenseUnionFind {
+    /// `len` elements, each in a component of its own.
+    pub fn new(len: usize) -> Self {
+        DenseUnionFind {
+            parent: (0..len).collect(),
+            size: vec![1; len],
+            components: len,
+        }
+    }
+
+    /// Adds a new element in a component of its own and returns it.
+    pub fn push(&mut self) -> usize {
+        let element = self.parent.len();
+        self.parent.push(element);
+        self.size.push(1);
+        self.components += 1;
+        element
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// The representative of the component containing `element`.
+    pub fn find(&mut self, element: usize) -> usize {
+        let mut root = element;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+
+        // Point everything on the way straight at the root.
+        let mut current = element;
+        while self.parent[current] != root {
+            let next = self.parent[current];
+            self.parent[current] = root;
+            current = next;
+        }
+        root
+    }
+
+    /// Merges the components of `a` and `b`. Returns `false` if they were already the same.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (root_a, root_b) = (self.find(a), self.find(b));
+        if root_a == root_b {
+            return false;
+        }
+
+        let (large, small) = if self.size[root_a] >= self.size[root_b] {
+            (root_a, root_b)
+        } else {
+            (root_b, root_a)
+        };
+        self.parent[small] = large;
+        self.size[large] += self.size[small];
+        self.components -= 1;
+        true
+    }
+
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    pub fn component_size(&mut self, element: usize) -> usize {
+        let root = self.find(element);
+        self.size[root]
+    }
+
+    pub fn component_count(&self) -> usize {
+        self.components
+    }
+
+    /// Every component, ordered by its smallest element, with elements in ascending order.
+    pub fn components(&mut self) -> Vec<Vec<usize>> {
+        let mut index_of_root: HashMap<usize, usize> = HashMap::new();
+        let mut components: Vec<Vec<usize>> = Vec::with_capacity(self.components);
+        for element in 0..self.len() {
+            let root = self.find(element);
+            let index = *index_of_root.entry(root).or_insert_with(|| {
+                components.push(Vec::new());
+                components.len() - 1
+            });
+            components[index].push(element);
+        }
+        components
+    }
+}
+
+/// Disjoint sets over arbitrary hashable keys. Keys are mapped to dense indices and handled by a
+/// `DenseUnionFind`, so prefer that directly when the elements already are `0..n`.
+#[derive(Debug, Clone)]
+pub struct UnionFind<K> {
+    indices: HashMap<K, usize>,
+    keys: Vec<K>,
+    sets: DenseUnionFind,
+}
+
+impl<K> Default for UnionFind<K> {
+    fn default() -> Self {
+        UnionFind {
+            indices: HashMap::new(),
+            keys: Vec::new(),
+            sets: DenseUnionFind::new(0),
+        }
+    }
+}
+
+impl<K> UnionFind<K>
+where
+    K: Eq + Hash + Clone,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `key` in a component of its own, unless it is already present.
+    pub fn insert(&mut self, key: &K) -> usize {
+        if let Some(&index) = self.indices.get(key) {
+            return index;
+        }
+        let index = self.sets.push();
+        self.indices.insert(key.clone(), index);
+        self.keys.push(key.clone());
+        index
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.indices.contains_key(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// The representative of the component containing `key`, or `None` for an unknown key.
+    pub fn find(&mut self, key: &K) -> Option<&K> {
+        let index = *self.indices.get(key)?;
+        let root = self.sets.find(index);
+        Some(&self.keys[root])
+    }
+
+    /// Merges the components of `a` and `b`, adding either key if it is missing. Returns `false`
+    /// if they were already the same.
+    pub fn union(&mut self, a: &K, b: &K) -> bool {
+        let (a, b) = (self.insert(a), self.insert(b));
+        self.sets.union(a, b)
+    }
+
+    pub fn connected(&mut self, a: &K, b: &K) -> bool {
+        match (self.indices.get(a), self.indices.get(b)) {
+            (Some(&a), Some(&b)) => self.sets.connected(a, b),
+            _ => false,
+        }
+    }
+
+    /// The number of keys in the component containing `key`, or 0 for an unknown key.
+    pub fn component_size(&mut self, key: &K) -> usize {
+        match self.indices.get(key) {
+            Some(&index) => self.sets.component_size(index),
+            None => 0,
+        }
+    }
+
+    pub fn component_count(&self) -> usize {
+        self.sets.component_count()
+    }
+
+    /// Every component, in the order their first key was inserted.
+    pub fn components(&mut self) -> Vec<Vec<K>> {
+        self.sets
+            .components()
+            .into_iter()
+            .map(|component| component.into_iter().map(|i| self.keys[i].clone()).collect())
+            .collect()
+    }
+}
+
+impl<K> FromIterator<K> for UnionFind<K>
+where
+    K: Eq + Hash + Clone,
+{
+    /// Every key in a component of its own.
+    fn from_iter<I: IntoIterator<Item = K>>(keys: I) -> Self {
+        let mut union_find = UnionFind::new();
+        for key in keys {
+            union_find.insert(&key);
+        }
+        union_find
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dense_union_find() {
+        let mut sets = DenseUnionFind::new(6);
+        assert!(sets.union(0, 1));
+        assert!(sets.union(2, 1));
+        assert!(!sets.union(0, 2));
+        assert!(sets.union(4, 5));
+        assert_eq!(sets.component_count(), 3);
+        assert_eq!(sets.component_size(2), 3);
+        assert!(sets.connected(0, 2));
+        assert!(!sets.connected(0, 3));
+        assert_eq!(sets.components(), vec![vec![0, 1, 2], vec![3], vec![4, 5]]);
+    }
+
+    #[test]
+    fn test_long_chain_is_compressed() {
+        let mut sets = DenseUnionFind::new(100_000);
+        for i in 1..100_000 {
+            sets.union(i - 1, i);
+        }
+        assert_eq!(sets.component_count(), 1);
+        assert_eq!(sets.component_size(0), 100_000);
+    }
+
+    #[test]
+    fn test_keyed_union_find() {
+        let mut sets: UnionFind<&str> = ["a", "b", "c"].into_iter().collect();
+        sets.union(&"a", &"c");
+        sets.union(&"d", &"e");
+        assert_eq!(sets.len(), 5);
+        assert_eq!(sets.component_count(), 3);
+        let root = sets.find(&"a").cloned();
+        assert_eq!(sets.find(&"c").cloned(), root);
+        assert_eq!(sets.find(&"z"), None);
+        assert_eq!(sets.component_size(&"e"), 2);
+        assert!(!sets.connected(&"a", &"z"));
+        assert_eq!(sets.components(), vec![vec!["a", "c"], vec!["b"], vec!["d", "e"]]);
+    }
+}